@@ -0,0 +1,470 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::parse::{
+    delimited, literal, map, pair, parse_all, quoted, unsigned, whitespace, ws, ParseError, Parser,
+};
+use crate::runner::Day;
+use crate::watch::run_part;
+
+/// Accepted answers by (day, part), as stored in `answers.toml`.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct RecordedAnswers {
+    pub answers: BTreeMap<(u32, u32), String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum AnswersError {
+    Parse {
+        line: usize,
+        err: ParseError,
+    },
+    /// A `partN = ...` line before any `[dayNN]` header.
+    OutsideDay {
+        line: usize,
+    },
+    InvalidPart {
+        line: usize,
+        part: u64,
+    },
+    Duplicate {
+        day: u32,
+        part: u32,
+    },
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnswersError::Parse { line, err } => write!(f, "line {}: {}", line, err),
+            AnswersError::OutsideDay { line } => {
+                write!(f, "line {}: answer outside of a [dayNN] section", line)
+            }
+            AnswersError::InvalidPart { line, part } => {
+                write!(f, "line {}: part must be 1 or 2: {}", line, part)
+            }
+            AnswersError::Duplicate { day, part } => {
+                write!(f, "day {} part {} is recorded more than once", day, part)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {}
+
+const HEADER: &str = "# Accepted answers for the puzzle inputs in src/bin, checked by `aoc verify`.
+# Regenerate with `aoc verify --record` only after confirming a new answer is correct.
+";
+
+fn header<'a>() -> impl Parser<'a, u64> {
+    delimited(literal("[day"), unsigned(), literal("]"))
+}
+
+/// `partN = "answer"`, as (N, answer).
+fn entry<'a>() -> impl Parser<'a, (u64, String)> {
+    map(
+        pair(
+            pair(literal("part"), whitespace(), unsigned()),
+            ws(literal("=")),
+            quoted(),
+        ),
+        |((_, part), answer)| (part, answer),
+    )
+}
+
+fn entry_line(part: u32, answer: &str) -> String {
+    let escaped = answer
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("part{} = \"{}\"", part, escaped)
+}
+
+impl RecordedAnswers {
+    /// Parse the small TOML subset used by `answers.toml`: comments, `[dayNN]` headers and
+    /// `partN = "answer"` lines. Line numbers in errors start at 1.
+    pub fn parse(text: &str) -> Result<RecordedAnswers, AnswersError> {
+        let header = header();
+        let entry = entry();
+
+        let mut out = RecordedAnswers::default();
+        let mut day = None;
+        for (i, line) in text.lines().enumerate() {
+            let line_no = i + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line.starts_with('[') {
+                let header_day = parse_all(&header, line)
+                    .map_err(|err| AnswersError::Parse { line: line_no, err })?;
+                day = Some(header_day as u32);
+                continue;
+            }
+            let (part, answer) = parse_all(&entry, line)
+                .map_err(|err| AnswersError::Parse { line: line_no, err })?;
+            let day = day.ok_or(AnswersError::OutsideDay { line: line_no })?;
+            if part != 1 && part != 2 {
+                return Err(AnswersError::InvalidPart {
+                    line: line_no,
+                    part,
+                });
+            }
+            let part = part as u32;
+            if out.answers.insert((day, part), answer).is_some() {
+                return Err(AnswersError::Duplicate { day, part });
+            }
+        }
+        Ok(out)
+    }
+
+    pub fn to_toml(&self) -> String {
+        self.merge_into(HEADER)
+    }
+
+    /// Rewrite `text`, an existing answers file, to hold these answers. Comments and layout are
+    /// kept: recorded answers are replaced in place, answers not held here are removed, and new
+    /// ones go at the end of their day's section or in a new section at the end.
+    pub fn merge_into(&self, text: &str) -> String {
+        let header = header();
+        let entry = entry();
+
+        let mut lines: Vec<String> = vec![];
+        let mut written = BTreeSet::new();
+        let mut sections = BTreeSet::new();
+        let mut day = None;
+        for line in text.lines() {
+            if let Ok(next_day) = parse_all(&header, line.trim()) {
+                if let Some(day) = day {
+                    self.append_unwritten(day, &mut lines, &mut written);
+                }
+                day = Some(next_day as u32);
+                sections.insert(next_day as u32);
+            } else if let (Some(day), Ok((part, _))) = (day, parse_all(&entry, line.trim())) {
+                let key = (day, part as u32);
+                if let Some(answer) = self.answers.get(&key) {
+                    lines.push(entry_line(key.1, answer));
+                    written.insert(key);
+                }
+                continue;
+            }
+            lines.push(line.to_string());
+        }
+        if let Some(day) = day {
+            self.append_unwritten(day, &mut lines, &mut written);
+        }
+        let days = self
+            .answers
+            .keys()
+            .map(|(day, _)| *day)
+            .filter(|day| !sections.contains(day))
+            .collect::<BTreeSet<u32>>();
+        for day in days {
+            lines.push(String::new());
+            lines.push(format!("[day{:02}]", day));
+            self.append_unwritten(day, &mut lines, &mut written);
+        }
+
+        let mut out = lines.join("\n");
+        out.push('\n');
+        out
+    }
+
+    /// Add the answers for `day` that are not yet in `lines`, before any trailing blank lines.
+    fn append_unwritten(
+        &self,
+        day: u32,
+        lines: &mut Vec<String>,
+        written: &mut BTreeSet<(u32, u32)>,
+    ) {
+        let at = lines.len()
+            - lines
+                .iter()
+                .rev()
+                .take_while(|line| line.trim().is_empty())
+                .count();
+        let new = self
+            .answers
+            .range((day, 0)..=(day, u32::MAX))
+            .filter(|(key, _)| written.insert(**key))
+            .map(|((_, part), answer)| entry_line(*part, answer))
+            .collect::<Vec<String>>();
+        lines.splice(at..at, new);
+    }
+}
+
+/// How a solver's answer compares with the recorded one.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Outcome {
+    Match(String),
+    Changed {
+        expected: String,
+        actual: String,
+    },
+    Failed {
+        expected: Option<String>,
+        error: String,
+    },
+    /// The solver ran but there is nothing recorded to compare with.
+    Unrecorded(String),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Check {
+    pub day: u32,
+    pub part: u32,
+    pub outcome: Outcome,
+}
+
+impl Check {
+    /// Whether a recorded answer was not reproduced. Errors from days with nothing recorded,
+    /// such as a freshly scaffolded one, are reported but do not fail.
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self.outcome,
+            Outcome::Changed { .. }
+                | Outcome::Failed {
+                    expected: Some(_),
+                    ..
+                }
+        )
+    }
+}
+
+/// One line per check, with changed answers shown as a `-`/`+` diff.
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = format!("day {} part {}", self.day, self.part);
+        match &self.outcome {
+            Outcome::Match(answer) => write!(f, "  {}: {}", name, answer),
+            Outcome::Changed { expected, actual } => {
+                write!(f, "- {}: {}\n+ {}: {}", name, expected, name, actual)
+            }
+            Outcome::Failed {
+                expected: Some(expected),
+                error,
+            } => write!(f, "- {}: {}\n! {}: {}", name, expected, name, error),
+            Outcome::Failed {
+                expected: None,
+                error,
+            } => write!(f, "! {}: {}", name, error),
+            Outcome::Unrecorded(answer) => write!(f, "? {}: {} (not recorded)", name, answer),
+        }
+    }
+}
+
+/// Run both parts of every day in `days` on the input returned by `input` and compare with
+/// `recorded`. A solver that panics fails like one that returns an error. Recorded answers for
+/// days that are not registered fail.
+pub fn verify(
+    days: &[Day],
+    recorded: &RecordedAnswers,
+    input: impl Fn(&Day) -> Result<String, String>,
+) -> Vec<Check> {
+    let mut out = vec![];
+    for day in days {
+        let input = input(day);
+        for (part, solver) in [(1, day.part1), (2, day.part2)] {
+            let expected = recorded.answers.get(&(day.day, part)).cloned();
+            let answer = input
+                .as_ref()
+                .map_err(|err| err.clone())
+                .and_then(|input| run_part(solver, input).0);
+            let outcome = match (answer, expected) {
+                (Err(error), expected) => Outcome::Failed { expected, error },
+                (Ok(actual), Some(expected)) if actual == expected => Outcome::Match(actual),
+                (Ok(actual), Some(expected)) => Outcome::Changed { expected, actual },
+                (Ok(actual), None) => Outcome::Unrecorded(actual),
+            };
+            out.push(Check {
+                day: day.day,
+                part,
+                outcome,
+            });
+        }
+    }
+    for ((day, part), expected) in &recorded.answers {
+        if !days.iter().any(|registered| registered.day == *day) {
+            out.push(Check {
+                day: *day,
+                part: *part,
+                outcome: Outcome::Failed {
+                    expected: Some(expected.clone()),
+                    error: "day is not registered".to_string(),
+                },
+            });
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::runner::{Answer, DAYS};
+
+    fn forty_two(_: &str) -> Answer {
+        Ok("42".to_string())
+    }
+
+    fn broken(_: &str) -> Answer {
+        Err("boom".into())
+    }
+
+    fn panics(_: &str) -> Answer {
+        panic!("solver blew up")
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        let recorded = RecordedAnswers::parse(include_str!("../answers.toml")).unwrap();
+        assert_eq!(recorded.answers.get(&(3, 2)), Some(&"84907174".to_string()));
+        assert_eq!(recorded.to_toml(), include_str!("../answers.toml"));
+    }
+
+    #[test]
+    fn test_to_toml_escapes() {
+        let recorded = RecordedAnswers {
+            answers: btreemap! {
+                (1, 1) => "say \"hi\"".to_string(),
+                (1, 2) => "a\\b\nc".to_string(),
+            },
+        };
+        let toml = recorded.to_toml();
+        assert!(toml.ends_with("[day01]\npart1 = \"say \\\"hi\\\"\"\npart2 = \"a\\\\b\\nc\"\n"));
+        assert_eq!(RecordedAnswers::parse(&toml), Ok(recorded));
+    }
+
+    #[test]
+    fn test_merge_into_keeps_comments() {
+        let text = "# my answers
+[day01]
+# checked by hand
+part1 = \"1\"
+
+[day03]
+  part2 = \"old\"
+";
+        let mut recorded = RecordedAnswers::parse(text).unwrap();
+        recorded.answers.insert((1, 2), "2".to_string());
+        recorded.answers.insert((3, 2), "new".to_string());
+        recorded.answers.insert((2, 1), "3".to_string());
+        assert_eq!(
+            recorded.merge_into(text),
+            "# my answers
+[day01]
+# checked by hand
+part1 = \"1\"
+part2 = \"2\"
+
+[day03]
+part2 = \"new\"
+
+[day02]
+part1 = \"3\"
+"
+        );
+        assert_eq!(
+            RecordedAnswers::parse(&recorded.merge_into(text)),
+            Ok(recorded)
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            RecordedAnswers::parse("part1 = \"1\"\n"),
+            Err(AnswersError::OutsideDay { line: 1 })
+        );
+        assert_eq!(
+            RecordedAnswers::parse("[day01]\npart3 = \"1\"\n"),
+            Err(AnswersError::InvalidPart { line: 2, part: 3 })
+        );
+        assert_eq!(
+            RecordedAnswers::parse("[day01]\npart1 = \"1\"\n[day1]\npart1 = \"2\"\n"),
+            Err(AnswersError::Duplicate { day: 1, part: 1 })
+        );
+        assert_eq!(
+            RecordedAnswers::parse("# answers\n[day01]\npart1 = 12\n")
+                .map_err(|err| err.to_string()),
+            Err("line 3: expected \"\\\"\" at column 8 but found \"12\"".to_string())
+        );
+    }
+
+    #[test]
+    fn test_verify() {
+        let days = [
+            Day::new(1, forty_two, broken),
+            Day::new(2, forty_two, forty_two),
+            Day::new(3, broken, forty_two),
+        ];
+        let recorded = RecordedAnswers {
+            answers: btreemap! {
+                (1, 1) => "42".to_string(),
+                (1, 2) => "7".to_string(),
+                (2, 1) => "41".to_string(),
+                (4, 1) => "1".to_string(),
+            },
+        };
+        let checks = verify(&days, &recorded, |_| Ok(String::new()));
+        assert_eq!(
+            checks
+                .iter()
+                .map(|check| check.to_string())
+                .collect::<Vec<String>>(),
+            vec![
+                "  day 1 part 1: 42",
+                "- day 1 part 2: 7\n! day 1 part 2: boom",
+                "- day 2 part 1: 41\n+ day 2 part 1: 42",
+                "? day 2 part 2: 42 (not recorded)",
+                "! day 3 part 1: boom",
+                "? day 3 part 2: 42 (not recorded)",
+                "- day 4 part 1: 1\n! day 4 part 1: day is not registered",
+            ]
+        );
+        assert_eq!(
+            checks
+                .iter()
+                .map(|check| check.is_failure())
+                .collect::<Vec<bool>>(),
+            vec![false, true, true, false, false, false, true]
+        );
+    }
+
+    #[test]
+    fn test_verify_panic() {
+        let recorded = RecordedAnswers {
+            answers: btreemap! {
+                (1, 1) => "42".to_string(),
+            },
+        };
+        let checks = verify(&[Day::new(1, panics, forty_two)], &recorded, |_| {
+            Ok(String::new())
+        });
+        assert_eq!(
+            checks
+                .iter()
+                .map(|check| check.to_string())
+                .collect::<Vec<String>>(),
+            vec![
+                "- day 1 part 1: 42\n! day 1 part 1: solver blew up",
+                "? day 1 part 2: 42 (not recorded)",
+            ]
+        );
+        assert!(checks[0].is_failure());
+    }
+
+    #[test]
+    fn test_recorded_answers_hold() {
+        let recorded = RecordedAnswers::parse(include_str!("../answers.toml")).unwrap();
+        let checks = verify(DAYS, &recorded, |day| {
+            std::fs::read_to_string(day.input_path()).map_err(|err| err.to_string())
+        });
+        // unrecorded or unimplemented days are fine here; only a changed answer is a regression
+        assert!(
+            checks.iter().all(|check| !check.is_failure()),
+            "{:?}",
+            checks
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use std::fmt;
+
+/// A value that does not fit in a `u64`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum OverflowError {
+    Add(u64, u64),
+    Mul(u64, u64),
+    Parse(String),
+    NotANumber(String),
+}
+
+impl fmt::Display for OverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OverflowError::Add(a, b) => write!(f, "overflow adding {} and {}", a, b),
+            OverflowError::Mul(a, b) => write!(f, "overflow multiplying {} and {}", a, b),
+            OverflowError::Parse(val) => write!(f, "number too large: {}", val),
+            OverflowError::NotANumber(val) => write!(f, "unable to parse number from {}", val),
+        }
+    }
+}
+
+impl std::error::Error for OverflowError {}
+
+pub fn checked_add(a: u64, b: u64) -> Result<u64, OverflowError> {
+    a.checked_add(b).ok_or(OverflowError::Add(a, b))
+}
+
+pub fn checked_mul(a: u64, b: u64) -> Result<u64, OverflowError> {
+    a.checked_mul(b).ok_or(OverflowError::Mul(a, b))
+}
+
+pub fn checked_sum(values: impl IntoIterator<Item = u64>) -> Result<u64, OverflowError> {
+    values.into_iter().try_fold(0, checked_add)
+}
+
+pub fn checked_product(values: impl IntoIterator<Item = u64>) -> Result<u64, OverflowError> {
+    values.into_iter().try_fold(1, checked_mul)
+}
+
+/// Parse an unsigned number, reporting values too large for a `u64` as overflow.
+pub fn parse_u64(val: &str) -> Result<u64, OverflowError> {
+    val.parse::<u64>().map_err(|err| match err.kind() {
+        std::num::IntErrorKind::PosOverflow => OverflowError::Parse(val.to_string()),
+        _ => OverflowError::NotANumber(val.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_checked_sum() {
+        assert_eq!(checked_sum(vec![u64::MAX - 1, 1]), Ok(u64::MAX));
+        assert_eq!(
+            checked_sum(vec![u64::MAX, 1]),
+            Err(OverflowError::Add(u64::MAX, 1))
+        );
+    }
+
+    #[test]
+    fn test_checked_product() {
+        assert_eq!(
+            checked_product(vec![1 << 32, (1 << 32) - 1]),
+            Ok(u64::MAX - (u32::MAX as u64))
+        );
+        assert_eq!(
+            checked_product(vec![1 << 32, 1 << 32]),
+            Err(OverflowError::Mul(1 << 32, 1 << 32))
+        );
+    }
+
+    #[test]
+    fn test_parse_u64() {
+        assert_eq!(parse_u64("18446744073709551615"), Ok(u64::MAX));
+        assert_eq!(
+            parse_u64("18446744073709551616"),
+            Err(OverflowError::Parse("18446744073709551616".to_string()))
+        );
+        assert_eq!(
+            parse_u64("12a"),
+            Err(OverflowError::NotANumber("12a".to_string()))
+        );
+        assert_eq!(parse_u64(""), Err(OverflowError::NotANumber(String::new())));
+    }
+}
@@ -0,0 +1,161 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use advent_2023::answers::{verify, Outcome, RecordedAnswers};
+use advent_2023::cli::Args;
+use advent_2023::runner::{find_day, Day, DAYS};
+use advent_2023::scaffold::new_day;
+use advent_2023::watch::{run_day, Watcher};
+
+const USAGE: &str = "usage:
+    aoc new --day n
+    aoc run [--day n] [--part 1|2] [input]
+    aoc verify [--answers path] [--record]
+    aoc watch --day n [--interval ms]";
+
+const ANSWERS_PATH: &str = "answers.toml";
+
+fn main() {
+    let args = Args::from_env(&["record"]);
+    match args.positional(0) {
+        Some("new") => new(&args),
+        Some("run") => run(&args),
+        Some("verify") => verify_answers(&args),
+        Some("watch") => watch(&args),
+        _ => panic!("{}", USAGE),
+    }
+}
+
+fn new(args: &Args) {
+    let day = args
+        .parse_value::<u32>("day")
+        .unwrap_or_else(|| panic!("{}", USAGE));
+    for path in new_day(Path::new("."), day).unwrap_or_else(|err| panic!("{}", err)) {
+        println!("wrote {}", path.display());
+    }
+}
+
+fn run(args: &Args) {
+    let days = match args.parse_value::<u32>("day") {
+        Some(day) => vec![find_day(day).unwrap_or_else(|| panic!("day {} is not registered", day))],
+        None => DAYS.iter().collect::<Vec<&Day>>(),
+    };
+    let parts = match args.parse_value::<u32>("part") {
+        Some(part @ (1 | 2)) => vec![part],
+        Some(part) => panic!("part must be 1 or 2: {}", part),
+        None => vec![1, 2],
+    };
+    for day in days {
+        let path = args
+            .positional(1)
+            .map(|path| path.to_string())
+            .unwrap_or_else(|| day.input_path());
+        // a freshly scaffolded day has no input yet, so report it like a solver error
+        let input = match std::fs::read_to_string(&path) {
+            Ok(input) => input,
+            Err(err) => {
+                println!("day {}: error: unable to read {}: {}", day.day, path, err);
+                continue;
+            }
+        };
+        for part in &parts {
+            let solver = if *part == 1 { day.part1 } else { day.part2 };
+            match solver(&input) {
+                Ok(answer) => println!("day {} part {}: {}", day.day, part, answer),
+                Err(err) => println!("day {} part {}: error: {}", day.day, part, err),
+            }
+        }
+    }
+}
+
+fn verify_answers(args: &Args) {
+    let path = args.value("answers").unwrap_or(ANSWERS_PATH);
+    // only recording may start from nothing; otherwise a missing file would verify nothing
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => Some(text),
+        Err(_) if args.flag("record") => None,
+        Err(err) => {
+            eprintln!("unable to read {}: {}", path, err);
+            std::process::exit(1);
+        }
+    };
+    let recorded = match &text {
+        Some(text) => RecordedAnswers::parse(text)
+            .unwrap_or_else(|err| panic!("unable to parse {}: {}", path, err)),
+        None => RecordedAnswers::default(),
+    };
+    let checks = verify(DAYS, &recorded, |day| {
+        std::fs::read_to_string(day.input_path())
+            .map_err(|err| format!("unable to read {}: {}", day.input_path(), err))
+    });
+    checks.iter().for_each(|check| println!("{}", check));
+
+    if args.flag("record") {
+        let mut updated = recorded.clone();
+        for check in &checks {
+            match &check.outcome {
+                Outcome::Match(answer)
+                | Outcome::Unrecorded(answer)
+                | Outcome::Changed { actual: answer, .. } => {
+                    updated
+                        .answers
+                        .insert((check.day, check.part), answer.clone());
+                }
+                Outcome::Failed { .. } => (),
+            }
+        }
+        let toml = match &text {
+            Some(text) => updated.merge_into(text),
+            None => updated.to_toml(),
+        };
+        std::fs::write(path, toml).expect("Unable to write answers");
+        println!("recorded answers in {}", path);
+        return;
+    }
+
+    let failures = checks.iter().filter(|check| check.is_failure()).count();
+    if failures > 0 {
+        eprintln!("{} of {} answers did not verify", failures, checks.len());
+        std::process::exit(1);
+    }
+}
+
+fn watch(args: &Args) {
+    let day = args
+        .parse_value::<u32>("day")
+        .unwrap_or_else(|| panic!("{}", USAGE));
+    let day = find_day(day).unwrap_or_else(|| panic!("day {} is not registered", day));
+    let interval = Duration::from_millis(args.parse_value("interval").unwrap_or(500));
+    let mut watcher = Watcher::new(
+        vec![PathBuf::from(day.input_path())],
+        vec![PathBuf::from(day.fixture_dir())],
+    );
+
+    println!(
+        "watching {} (every {}ms, ctrl-c to stop)",
+        watcher
+            .paths()
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<String>>()
+            .join(", "),
+        interval.as_millis()
+    );
+    run_day(Path::new("."), day)
+        .iter()
+        .for_each(|run| println!("{}", run));
+    loop {
+        std::thread::sleep(interval);
+        let changed = watcher.poll();
+        if changed.is_empty() {
+            continue;
+        }
+        println!();
+        for path in changed {
+            println!("changed: {}", path.display());
+        }
+        run_day(Path::new("."), day)
+            .iter()
+            .for_each(|run| println!("{}", run));
+    }
+}
@@ -0,0 +1,166 @@
+use advent_2023::cli::{open_input, Args};
+use advent_2023::day02::adjust::{get_max_reduction, get_required_growth};
+use advent_2023::day02::eval::Evaluator;
+use advent_2023::day02::generate::{generate, GeneratorConfig};
+use advent_2023::day02::stats::GameStats;
+use advent_2023::day02::validate::IdValidator;
+use advent_2023::day02::{is_possible_game, Draw, Game, PUZZLE_BAG};
+use advent_2023::day02::{parse_games, read_games};
+
+const USAGE: &str = "usage:
+    day02 adjust [--bag red,green,blue] [--games id,id,...] [input]
+    day02 generate [--seed n] [--games n] [--min-draws n] [--max-draws n]
+                   [--colors red,green,blue] [--max-count n] [--bag red,green,blue]
+                   [--answers path] [output]
+    day02 solve [--bag red,green,blue] [--strict] [input|-]
+    day02 stats [--format table|csv|json] [input]
+    day02 validate [input|-]";
+
+fn main() {
+    let args = Args::from_env(&["strict"]);
+    match args.positional(0) {
+        Some("adjust") => adjust(&args),
+        Some("generate") => generate_log(&args),
+        Some("solve") => solve(&args),
+        Some("stats") => stats(&args),
+        Some("validate") => validate(&args),
+        _ => panic!("{}", USAGE),
+    }
+}
+
+fn adjust(args: &Args) {
+    let bag = args.parse_value::<Draw>("bag").unwrap_or(PUZZLE_BAG);
+    let games = read_games(args.positional(1).unwrap_or("src/bin/day02.txt"))
+        .unwrap_or_else(|err| panic!("{}", err));
+
+    for growth in get_required_growth(&games, &bag) {
+        println!(
+            "Game {} needs +{} red, +{} green, +{} blue",
+            growth.id, growth.growth.red, growth.growth.green, growth.growth.blue
+        );
+    }
+
+    // without an explicit selection, keep every game that is currently possible
+    let chosen = match args.value("games") {
+        Some(ids) => {
+            let ids = ids
+                .split(',')
+                .map(|id| {
+                    id.parse::<u64>()
+                        .unwrap_or_else(|_| panic!("unable to parse id: {}", id))
+                })
+                .collect::<Vec<u64>>();
+            games
+                .iter()
+                .filter(|game| ids.contains(&game.id))
+                .collect::<Vec<&Game>>()
+        }
+        None => games
+            .iter()
+            .filter(|game| is_possible_game(game, &bag))
+            .collect::<Vec<&Game>>(),
+    };
+    match get_max_reduction(chosen.iter().copied(), &bag) {
+        Ok(reduction) => println!(
+            "Largest reduction keeping {} games possible: -{} red, -{} green, -{} blue",
+            chosen.len(),
+            reduction.red,
+            reduction.green,
+            reduction.blue
+        ),
+        Err(ids) => println!(
+            "No reduction possible, games already impossible: {}",
+            ids.iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+    }
+}
+
+fn generate_log(args: &Args) {
+    let defaults = GeneratorConfig::default();
+    let config = GeneratorConfig {
+        seed: args.parse_value("seed").unwrap_or(defaults.seed),
+        games: args.parse_value("games").unwrap_or(defaults.games),
+        min_draws: args.parse_value("min-draws").unwrap_or(defaults.min_draws),
+        max_draws: args.parse_value("max-draws").unwrap_or(defaults.max_draws),
+        colors: args
+            .value("colors")
+            .map(|colors| colors.split(',').map(|color| color.to_string()).collect())
+            .unwrap_or(defaults.colors),
+        max_count: args.parse_value("max-count").unwrap_or(defaults.max_count),
+    };
+    let bag = args.parse_value::<Draw>("bag").unwrap_or(PUZZLE_BAG);
+    let generated = generate(&config, &bag).unwrap_or_else(|err| panic!("{}", err));
+
+    // answers are written in the same format as `day02 solve` so fixtures can be diffed directly
+    let answers = format!(
+        "{}\n{}\n",
+        generated.answers.possible_id_sum, generated.answers.total_power
+    );
+    match args.value("answers") {
+        Some(path) => std::fs::write(path, answers).expect("Unable to write answers"),
+        None => eprint!("{}", answers),
+    }
+    match args.positional(1) {
+        Some(path) => std::fs::write(path, generated.log).expect("Unable to write log"),
+        None => print!("{}", generated.log),
+    }
+}
+
+fn solve(args: &Args) {
+    let bag = args.parse_value::<Draw>("bag").unwrap_or(PUZZLE_BAG);
+    let mut evaluator = Evaluator::new(bag);
+    // checking IDs keeps every ID seen, so only pay for it when asked
+    let mut validator = args.flag("strict").then(IdValidator::default);
+    for game in parse_games(open_input(
+        args.positional(1).unwrap_or("src/bin/day02.txt"),
+    )) {
+        let game = game.unwrap_or_else(|err| panic!("{}", err));
+        if let Some(validator) = validator.as_mut() {
+            validator.push(game.id);
+        }
+        evaluator
+            .push(&game)
+            .unwrap_or_else(|err| panic!("{}", err));
+    }
+
+    if let Some(validator) = validator {
+        let issues = validator.finish();
+        issues.iter().for_each(|issue| eprintln!("{}", issue));
+        if !issues.is_empty() {
+            eprintln!("refusing to answer for an inconsistent log");
+            std::process::exit(1);
+        }
+    }
+
+    let answers = evaluator.finish();
+    println!("{}", answers.possible_id_sum);
+    println!("{}", answers.total_power);
+}
+
+fn stats(args: &Args) {
+    let games = read_games(args.positional(1).unwrap_or("src/bin/day02.txt"))
+        .unwrap_or_else(|err| panic!("{}", err));
+    let stats = GameStats::from_games(&games).unwrap_or_else(|err| panic!("{}", err));
+    match args.value("format").unwrap_or("table") {
+        "table" => print!("{}", stats.to_table()),
+        "csv" => print!("{}", stats.to_csv()),
+        "json" => println!("{}", stats.to_json()),
+        format => panic!("unknown format {}\n{}", format, USAGE),
+    }
+}
+
+fn validate(args: &Args) {
+    let mut validator = IdValidator::default();
+    parse_games(open_input(
+        args.positional(1).unwrap_or("src/bin/day02.txt"),
+    ))
+    .for_each(|game| validator.push(game.unwrap_or_else(|err| panic!("{}", err)).id));
+    let issues = validator.finish();
+    issues.iter().for_each(|issue| println!("{}", issue));
+    if !issues.is_empty() {
+        std::process::exit(1);
+    }
+}
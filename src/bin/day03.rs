@@ -0,0 +1,389 @@
+use advent_2023::cli::{open_input, Args};
+use advent_2023::day03::diagnostics::get_diagnostics;
+use advent_2023::day03::dialect::{solve_decimal, Dialect};
+use advent_2023::day03::diff::diff;
+use advent_2023::day03::generate::{generate, GeneratorConfig};
+use advent_2023::day03::graph::SchematicGraph;
+use advent_2023::day03::neighborhood::Neighborhood;
+use advent_2023::day03::render::{render, Window};
+use advent_2023::day03::rules::{apply_rules, Rule};
+use advent_2023::day03::spatial::{Element, Metric, SpatialIndex};
+use advent_2023::day03::stream::{solve_stream, Event};
+use advent_2023::day03::svg::to_svg;
+use advent_2023::day03::symbols::SymbolSet;
+use advent_2023::day03::{get_part_spans, get_symbols, read_lines, solve as solve_lines};
+use advent_2023::grid::{read_grid, GridOptions, RaggedPolicy};
+
+const USAGE: &str = "usage:
+    day03 diagnose [--neighborhood spec] [--wrap] [input]
+    day03 diff [--neighborhood spec] [--wrap] old new
+    day03 generate [--seed n] [--height n] [--width n] [--density p] [--min-len n]
+                   [--max-len n] [--symbol-chance p] [--gear-chance p] [--symbols chars]
+                   [--answers path] [output]
+    day03 graph [--dot] [--symbol line,col] [--number line,col] [--neighborhood spec] [--wrap]
+                [input]
+    day03 grid [--reject] [input|-]
+    day03 query (--within d | --nearest) --at line,col [--metric manhattan|chebyshev] [input]
+    day03 query --rect top,left,bottom,right [input]
+    day03 render [--plain] [--at line,col] [--radius n] [input]
+    day03 rules [--rule symbol:count:aggregation]... [--neighborhood spec] [--wrap] [input]
+    day03 solve [--dialect classic|signed] [--neighborhood spec] [--wrap] [input]
+    day03 stream [--events] [input|-]
+    day03 svg [--output path] [input]
+
+neighborhood spec is 4, 8 (default), r<radius> or offsets like -1:0,1:0";
+
+fn main() {
+    let args = Args::from_env(&["dot", "events", "nearest", "plain", "reject", "wrap"]);
+    match args.positional(0) {
+        Some("diagnose") => diagnose(&args),
+        Some("diff") => diff_schematics(&args),
+        Some("generate") => generate_schematic(&args),
+        Some("graph") => graph(&args),
+        Some("grid") => grid(&args),
+        Some("query") => query(&args),
+        Some("render") => render_schematic(&args),
+        Some("rules") => rules(&args),
+        Some("solve") => solve(&args),
+        Some("stream") => stream(&args),
+        Some("svg") => svg(&args),
+        _ => panic!("{}", USAGE),
+    }
+}
+
+fn neighborhood(args: &Args, lines: &[String]) -> Neighborhood {
+    let neighborhood = args
+        .parse_value::<Neighborhood>("neighborhood")
+        .unwrap_or_default();
+    if args.flag("wrap") {
+        let width = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        neighborhood.with_wraparound(lines.len(), width)
+    } else {
+        neighborhood
+    }
+}
+
+fn diagnose(args: &Args) {
+    let lines = read_lines(args.positional(1).unwrap_or("src/bin/day03.txt"));
+    let symbol_set = SymbolSet::default();
+    let spans = get_part_spans(&lines, &symbol_set).unwrap_or_else(|err| panic!("{}", err));
+    let symbols = get_symbols(&lines, &symbol_set).unwrap_or_else(|err| panic!("{}", err));
+    get_diagnostics(&spans, &symbols, &neighborhood(args, &lines))
+        .iter()
+        .for_each(|diagnostic| println!("{}", diagnostic));
+}
+
+fn diff_schematics(args: &Args) {
+    let (Some(old_path), Some(new_path)) = (args.positional(1), args.positional(2)) else {
+        panic!("{}", USAGE);
+    };
+    let old_lines = read_lines(old_path);
+    let new_lines = read_lines(new_path);
+    let diff = diff(
+        &old_lines,
+        &new_lines,
+        &SymbolSet::default(),
+        &neighborhood(args, &new_lines),
+    )
+    .unwrap_or_else(|err| panic!("{}", err));
+    diff.changes
+        .iter()
+        .for_each(|change| println!("{}", change));
+    println!(
+        "part sum: {} -> {} ({:+})",
+        diff.part_sum.0,
+        diff.part_sum.1,
+        diff.part_sum.1 as i128 - diff.part_sum.0 as i128
+    );
+    println!(
+        "gear sum: {} -> {} ({:+})",
+        diff.gear_sum.0,
+        diff.gear_sum.1,
+        diff.gear_sum.1 as i128 - diff.gear_sum.0 as i128
+    );
+}
+
+fn generate_schematic(args: &Args) {
+    let defaults = GeneratorConfig::default();
+    let config = GeneratorConfig {
+        seed: args.parse_value("seed").unwrap_or(defaults.seed),
+        height: args.parse_value("height").unwrap_or(defaults.height),
+        width: args.parse_value("width").unwrap_or(defaults.width),
+        density: args.parse_value("density").unwrap_or(defaults.density),
+        min_len: args.parse_value("min-len").unwrap_or(defaults.min_len),
+        max_len: args.parse_value("max-len").unwrap_or(defaults.max_len),
+        symbol_chance: args
+            .parse_value("symbol-chance")
+            .unwrap_or(defaults.symbol_chance),
+        gear_chance: args
+            .parse_value("gear-chance")
+            .unwrap_or(defaults.gear_chance),
+        symbols: args
+            .value("symbols")
+            .map(|symbols| symbols.chars().collect())
+            .unwrap_or(defaults.symbols),
+    };
+    let generated = generate(&config).unwrap_or_else(|err| panic!("{}", err));
+
+    // answers are written in the same format as `day03 solve` so fixtures can be diffed directly
+    let answers = format!("{}\n{}\n", generated.part_sum, generated.gear_sum);
+    match args.value("answers") {
+        Some(path) => std::fs::write(path, answers).expect("Unable to write answers"),
+        None => eprint!("{}", answers),
+    }
+    let schematic = generated
+        .lines
+        .iter()
+        .map(|line| format!("{}\n", line))
+        .collect::<String>();
+    match args.positional(1) {
+        Some(path) => std::fs::write(path, schematic).expect("Unable to write schematic"),
+        None => print!("{}", schematic),
+    }
+}
+
+fn parse_coord(val: &str) -> (usize, usize) {
+    let (line, col) = val
+        .split_once(',')
+        .unwrap_or_else(|| panic!("expected line,col but got: {}", val));
+    (
+        line.parse()
+            .unwrap_or_else(|_| panic!("unable to parse line: {}", line)),
+        col.parse()
+            .unwrap_or_else(|_| panic!("unable to parse col: {}", col)),
+    )
+}
+
+fn graph(args: &Args) {
+    let lines = read_lines(args.positional(1).unwrap_or("src/bin/day03.txt"));
+    let symbol_set = SymbolSet::default();
+    let spans = get_part_spans(&lines, &symbol_set).unwrap_or_else(|err| panic!("{}", err));
+    let symbols = get_symbols(&lines, &symbol_set).unwrap_or_else(|err| panic!("{}", err));
+    let graph = SchematicGraph::new(&spans, &symbols, &neighborhood(args, &lines));
+
+    if args.flag("dot") {
+        print!("{}", graph.to_dot());
+        return;
+    }
+    if let Some(at) = args.value("symbol") {
+        let (line, col) = parse_coord(at);
+        let symbol_i = graph
+            .symbol_at(line, col)
+            .unwrap_or_else(|| panic!("no symbol at {}", at));
+        for span in graph.symbol_neighbors(symbol_i) {
+            println!("{} at ({}, {})", span.value, span.line, span.start);
+        }
+        return;
+    }
+    if let Some(at) = args.value("number") {
+        let (line, col) = parse_coord(at);
+        let number_i = graph
+            .number_at(line, col)
+            .unwrap_or_else(|| panic!("no number at {}", at));
+        for symbol in graph.number_neighbors(number_i) {
+            println!("{} at ({}, {})", symbol.symbol, symbol.line, symbol.col);
+        }
+        return;
+    }
+
+    let components = graph.connected_components();
+    let (symbol_degrees, number_degrees) = graph.degree_distribution();
+    println!(
+        "{} symbols, {} numbers, {} components ({} with more than one node)",
+        graph.symbols.len(),
+        graph.numbers.len(),
+        components.len(),
+        components
+            .iter()
+            .filter(|component| component.len() > 1)
+            .count()
+    );
+    println!("symbol degrees:");
+    symbol_degrees
+        .iter()
+        .for_each(|(degree, count)| println!("{:>6}{:>8}", degree, count));
+    println!("number degrees:");
+    number_degrees
+        .iter()
+        .for_each(|(degree, count)| println!("{:>6}{:>8}", degree, count));
+}
+
+fn grid(args: &Args) {
+    let options = GridOptions {
+        ragged: if args.flag("reject") {
+            RaggedPolicy::Reject
+        } else {
+            GridOptions::default().ragged
+        },
+        ..GridOptions::default()
+    };
+    let grid = read_grid(
+        open_input(args.positional(1).unwrap_or("src/bin/day03.txt")),
+        &options,
+    )
+    .unwrap_or_else(|err| panic!("{}", err));
+    println!("{} rows, width {}", grid.lines.len(), grid.width);
+    for (first, last, width) in grid.width_runs() {
+        if first == last {
+            println!("line {}: width {}", first, width);
+        } else {
+            println!("lines {}-{}: width {}", first, last, width);
+        }
+    }
+}
+
+fn print_element(element: &Element) {
+    match element {
+        Element::Number(span) => println!("{} at ({}, {})", span.value, span.line, span.start),
+        Element::Symbol(node) => println!("{} at ({}, {})", node.symbol, node.line, node.col),
+    }
+}
+
+fn query(args: &Args) {
+    let lines = read_lines(args.positional(1).unwrap_or("src/bin/day03.txt"));
+    let symbol_set = SymbolSet::default();
+    let spans = get_part_spans(&lines, &symbol_set).unwrap_or_else(|err| panic!("{}", err));
+    let symbols = get_symbols(&lines, &symbol_set).unwrap_or_else(|err| panic!("{}", err));
+    let index = SpatialIndex::new(&spans, &symbols);
+
+    if let Some(rect) = args.value("rect") {
+        let bounds = rect
+            .split(',')
+            .map(|val| {
+                val.parse::<usize>()
+                    .unwrap_or_else(|_| panic!("unable to parse bound: {}", val))
+            })
+            .collect::<Vec<usize>>();
+        let [top, left, bottom, right] = bounds[..] else {
+            panic!("expected top,left,bottom,right but got: {}", rect);
+        };
+        index
+            .in_rect(top, left, bottom, right)
+            .into_iter()
+            .for_each(print_element);
+        return;
+    }
+
+    let (line, col) = parse_coord(args.value("at").unwrap_or_else(|| panic!("{}", USAGE)));
+    let metric = args
+        .parse_value::<Metric>("metric")
+        .unwrap_or(Metric::Chebyshev);
+    if args.flag("nearest") {
+        match index.nearest(line, col, metric, |_| true) {
+            Some((element, dist)) => {
+                print!("distance {}: ", dist);
+                print_element(element);
+            }
+            None => println!("schematic is empty"),
+        }
+    } else {
+        let d = args
+            .parse_value::<usize>("within")
+            .unwrap_or_else(|| panic!("{}", USAGE));
+        index
+            .within(line, col, d, metric)
+            .into_iter()
+            .for_each(print_element);
+    }
+}
+
+fn render_schematic(args: &Args) {
+    let lines = read_lines(args.positional(1).unwrap_or("src/bin/day03.txt"));
+    let window = args.value("at").map(|at| {
+        let (line, col) = parse_coord(at);
+        Window {
+            line,
+            col,
+            radius: args.parse_value("radius").unwrap_or(5),
+        }
+    });
+    print!(
+        "{}",
+        render(
+            &lines,
+            &SymbolSet::default(),
+            !args.flag("plain"),
+            window.as_ref()
+        )
+        .unwrap_or_else(|err| panic!("{}", err))
+    );
+}
+
+fn rules(args: &Args) {
+    let lines = read_lines(args.positional(1).unwrap_or("src/bin/day03.txt"));
+    let symbol_set = SymbolSet::default();
+    let rules = match args.values("rule")[..] {
+        [] => vec![Rule::gear_ratio()],
+        ref rules => rules
+            .iter()
+            .map(|rule| rule.parse::<Rule>().unwrap_or_else(|err| panic!("{}", err)))
+            .collect(),
+    };
+    let spans = get_part_spans(&lines, &symbol_set).unwrap_or_else(|err| panic!("{}", err));
+    let symbols = get_symbols(&lines, &symbol_set).unwrap_or_else(|err| panic!("{}", err));
+    let totals = apply_rules(&spans, &symbols, &rules, &neighborhood(args, &lines))
+        .unwrap_or_else(|err| panic!("{}", err));
+
+    println!("{:<16}{:>10}{:>20}", "rule", "matches", "total");
+    for total in totals {
+        println!(
+            "{:<16}{:>10}{:>20}",
+            total.rule.to_string(),
+            total.matches,
+            total.total
+        );
+    }
+}
+
+fn solve(args: &Args) {
+    let lines = read_lines(args.positional(1).unwrap_or("src/bin/day03.txt"));
+    let symbol_set = SymbolSet::default();
+    let neighborhood = neighborhood(args, &lines);
+    if args.parse_value::<Dialect>("dialect").unwrap_or_default() == Dialect::Signed {
+        let (part_sum, gear_sum) = solve_decimal(&lines, &symbol_set, &neighborhood)
+            .unwrap_or_else(|err| panic!("{}", err));
+        println!("{}", part_sum);
+        println!("{}", gear_sum);
+        return;
+    }
+    let (part_sum, gear_sum) =
+        solve_lines(&lines, &symbol_set, &neighborhood).unwrap_or_else(|err| panic!("{}", err));
+    println!("{}", part_sum);
+    println!("{}", gear_sum);
+}
+
+fn stream(args: &Args) {
+    let events = args.flag("events");
+    let (part_sum, gear_sum) = solve_stream(
+        open_input(args.positional(1).unwrap_or("src/bin/day03.txt")),
+        SymbolSet::default(),
+        |event| {
+            if events {
+                match event {
+                    Event::PartNumber(span) => {
+                        println!("part {} at ({}, {})", span.value, span.line, span.start)
+                    }
+                    Event::GearRatio { line, col, ratio } => {
+                        println!("gear {} at ({}, {})", ratio, line, col)
+                    }
+                }
+            }
+        },
+    )
+    .unwrap_or_else(|err| panic!("{}", err));
+    println!("{}", part_sum);
+    println!("{}", gear_sum);
+}
+
+fn svg(args: &Args) {
+    let lines = read_lines(args.positional(1).unwrap_or("src/bin/day03.txt"));
+    let svg = to_svg(&lines, &SymbolSet::default()).unwrap_or_else(|err| panic!("{}", err));
+    match args.value("output") {
+        Some(path) => std::fs::write(path, svg).expect("Unable to write svg"),
+        None => print!("{}", svg),
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::str::FromStr;
+
+/// Minimal command line parser shared by the puzzle binaries.
+///
+/// Arguments of the form `--name value` are options, which may be repeated, bare `--name`
+/// arguments listed in `flags` are switches and everything else is positional.
+#[derive(Debug, PartialEq)]
+pub struct Args {
+    pub positional: Vec<String>,
+    options: HashMap<String, Vec<String>>,
+    switches: Vec<String>,
+}
+
+impl Args {
+    pub fn parse(args: impl IntoIterator<Item = String>, flags: &[&str]) -> Args {
+        let mut positional = Vec::new();
+        let mut options = HashMap::new();
+        let mut switches = Vec::new();
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.strip_prefix("--") {
+                Some(name) if flags.contains(&name) => switches.push(name.to_string()),
+                Some(name) => {
+                    let value = args
+                        .next()
+                        .unwrap_or_else(|| panic!("missing value for option --{}", name));
+                    options
+                        .entry(name.to_string())
+                        .or_insert_with(Vec::new)
+                        .push(value);
+                }
+                None => positional.push(arg),
+            }
+        }
+
+        Args {
+            positional,
+            options,
+            switches,
+        }
+    }
+
+    pub fn from_env(flags: &[&str]) -> Args {
+        Args::parse(std::env::args().skip(1), flags)
+    }
+
+    pub fn positional(&self, i: usize) -> Option<&str> {
+        self.positional.get(i).map(|val| val.as_str())
+    }
+
+    /// The last value given for option `name`.
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.values(name).last().copied()
+    }
+
+    /// Every value given for option `name`, in order.
+    pub fn values(&self, name: &str) -> Vec<&str> {
+        self.options
+            .get(name)
+            .map(|vals| vals.iter().map(|val| val.as_str()).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        self.switches.iter().any(|val| val == name)
+    }
+
+    pub fn parse_value<T: FromStr>(&self, name: &str) -> Option<T> {
+        self.value(name).map(|val| {
+            val.parse::<T>()
+                .unwrap_or_else(|_| panic!("invalid value for --{}: {}", name, val))
+        })
+    }
+}
+
+/// Open `path` for buffered reading, with `-` meaning stdin.
+pub fn open_input(path: &str) -> Box<dyn BufRead> {
+    if path == "-" {
+        Box::new(BufReader::new(std::io::stdin()))
+    } else {
+        Box::new(BufReader::new(File::open(path).unwrap_or_else(|err| {
+            panic!("Unable to open {}: {}", path, err)
+        })))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(line: &str, flags: &[&str]) -> Args {
+        Args::parse(line.split(' ').map(|val| val.to_string()), flags)
+    }
+
+    #[test]
+    fn test_parse() {
+        let args = args("stats --format json --strict input.txt", &["strict"]);
+        assert_eq!(args.positional(0), Some("stats"));
+        assert_eq!(args.positional(1), Some("input.txt"));
+        assert_eq!(args.value("format"), Some("json"));
+        assert!(args.flag("strict"));
+        assert!(!args.flag("format"));
+    }
+
+    #[test]
+    fn test_parse_repeated() {
+        let args = args("--rule a --rule b", &[]);
+        assert_eq!(args.values("rule"), vec!["a", "b"]);
+        assert_eq!(args.value("rule"), Some("b"));
+        assert_eq!(args.values("other"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_parse_value() {
+        let args = args("--games 12", &[]);
+        assert_eq!(args.parse_value::<u32>("games"), Some(12));
+        assert_eq!(args.parse_value::<u32>("draws"), None);
+    }
+}
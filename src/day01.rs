@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+
+use crate::runner::Answer;
+
+pub fn read_lines(path: &str) -> Vec<String> {
+    std::fs::read_to_string(path)
+        .map(|file| {
+            file.lines()
+                .filter(|line| !line.is_empty())
+                .map(|val| val.to_string())
+                .collect::<Vec<String>>()
+        })
+        .expect("Unable to open file")
+}
+
+/// The first and last digit of `line` as a two-digit number, or `None` if it has no digits.
+pub fn get_calibration(line: &str) -> Option<u16> {
+    let mut digits = line.chars().filter_map(|c| c.to_digit(10));
+    let first = digits.next()? as u16;
+    let last = digits.next_back().map_or(first, |digit| digit as u16);
+    Some(first * 10u16 + last)
+}
+
+fn get_digit(line: &str, start_i: usize, lookup: &HashMap<&str, u16>) -> Option<u16> {
+    lookup.iter().find_map(|(needle, val)| {
+        if start_i + needle.len() > line.len() {
+            None
+        } else if &line.get(start_i..start_i + needle.len()).unwrap() == needle {
+            Some(*val)
+        } else {
+            None
+        }
+    })
+}
+
+/// Like [`get_calibration`], but digits may also be spelled out, e.g. `two1nine` is 29.
+pub fn get_spelled_calibration(line: &str) -> Option<u16> {
+    let lookup = hashmap! {
+        "one" => 1u16,
+        "two" => 2u16,
+        "three" => 3u16,
+        "four" => 4u16,
+        "five" => 5u16,
+        "six" => 6u16,
+        "seven" => 7u16,
+        "eight" => 8u16,
+        "nine" => 9u16,
+        "1" => 1u16,
+        "2" => 2u16,
+        "3" => 3u16,
+        "4" => 4u16,
+        "5" => 5u16,
+        "6" => 6u16,
+        "7" => 7u16,
+        "8" => 8u16,
+        "9" => 9u16,
+        "0" => 0u16,
+    };
+
+    let first = line
+        .char_indices()
+        .find_map(|(i, _)| get_digit(line, i, &lookup))?;
+    let last = line
+        .char_indices()
+        .rev()
+        .find_map(|(i, _)| get_digit(line, i, &lookup))?;
+
+    Some(first * 10u16 + last)
+}
+
+/// The sum of `calibration` over every non-empty line of `input`.
+fn sum_calibrations(input: &str, calibration: fn(&str) -> Option<u16>) -> Answer {
+    Ok(input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            calibration(line)
+                .map(u64::from)
+                .ok_or_else(|| format!("could not find digit in {}", line))
+        })
+        .sum::<Result<u64, String>>()?
+        .to_string())
+}
+
+pub fn part1(input: &str) -> Answer {
+    sum_calibrations(input, get_calibration)
+}
+
+pub fn part2(input: &str) -> Answer {
+    sum_calibrations(input, get_spelled_calibration)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        assert_eq!(get_calibration("1abc2"), Some(12),)
+    }
+
+    #[test]
+    fn test2() {
+        assert_eq!(get_calibration("pqr3stu8vwx"), Some(38),)
+    }
+
+    #[test]
+    fn test3() {
+        assert_eq!(get_calibration("a1b2c3d4e5f"), Some(15),)
+    }
+
+    #[test]
+    fn test4() {
+        assert_eq!(get_calibration("treb7uchet"), Some(77),)
+    }
+
+    #[test]
+    fn test_no_digits() {
+        assert_eq!(get_calibration("trebuchet"), None);
+        assert_eq!(
+            part1("1abc2\ntrebuchet\n").unwrap_err().to_string(),
+            "could not find digit in trebuchet"
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_spelled {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        assert_eq!(get_spelled_calibration("two1nine"), Some(29),)
+    }
+
+    #[test]
+    fn test2() {
+        assert_eq!(get_spelled_calibration("eightwothree"), Some(83),)
+    }
+
+    #[test]
+    fn test3() {
+        assert_eq!(get_spelled_calibration("xtwone3four"), Some(24),)
+    }
+
+    #[test]
+    fn test4() {
+        assert_eq!(get_spelled_calibration("4nineeightseven2"), Some(42),)
+    }
+
+    #[test]
+    fn test5() {
+        assert_eq!(get_spelled_calibration("zoneight234"), Some(14),)
+    }
+
+    #[test]
+    fn test6() {
+        assert_eq!(get_spelled_calibration("7pqrstsixteen"), Some(76),)
+    }
+
+    #[test]
+    fn test_no_digits() {
+        assert_eq!(get_spelled_calibration("trebuchet"), None);
+        assert!(part2("two1nine\ntrebuchet\n").is_err());
+    }
+}
@@ -0,0 +1,555 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::arith::{checked_add, checked_product, OverflowError};
+use crate::parse::{
+    literal, map, pair, parse_all, separated, unsigned, whitespace, word, ws, ParseError,
+};
+use crate::runner::Answer;
+
+pub mod adjust;
+pub mod eval;
+pub mod generate;
+pub mod stats;
+pub mod validate;
+
+/// The bag given in the puzzle for part 1.
+pub const PUZZLE_BAG: Draw = Draw {
+    red: 12,
+    green: 13,
+    blue: 14,
+};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum GameError {
+    Read(String),
+    Parse(ParseError),
+    Overflow(OverflowError),
+}
+
+impl From<ParseError> for GameError {
+    fn from(err: ParseError) -> GameError {
+        GameError::Parse(err)
+    }
+}
+
+impl From<OverflowError> for GameError {
+    fn from(err: OverflowError) -> GameError {
+        GameError::Overflow(err)
+    }
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameError::Read(err) => write!(f, "unable to read line: {}", err),
+            GameError::Parse(err) => write!(f, "{}", err),
+            GameError::Overflow(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
+
+pub fn is_possible_game(game: &Game, bag_contents: &Draw) -> bool {
+    game.draws.iter().all(|draw| draw <= bag_contents)
+}
+
+pub fn get_smallest_bag(game: &Game) -> Draw {
+    game.draws.iter().collect()
+}
+
+pub fn get_power(bag: &Draw) -> Result<u64, OverflowError> {
+    checked_product([bag.red, bag.green, bag.blue])
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Draw {
+    pub red: u64,
+    pub green: u64,
+    pub blue: u64,
+}
+
+impl Draw {
+    /// Elementwise max: the smallest bag able to produce both draws.
+    pub fn join(&self, other: &Draw) -> Draw {
+        Draw {
+            red: self.red.max(other.red),
+            green: self.green.max(other.green),
+            blue: self.blue.max(other.blue),
+        }
+    }
+
+    /// Elementwise min: the largest draw that both bags are able to produce.
+    pub fn meet(&self, other: &Draw) -> Draw {
+        Draw {
+            red: self.red.min(other.red),
+            green: self.green.min(other.green),
+            blue: self.blue.min(other.blue),
+        }
+    }
+
+    pub fn checked_add(&self, other: &Draw) -> Result<Draw, OverflowError> {
+        Ok(Draw {
+            red: checked_add(self.red, other.red)?,
+            green: checked_add(self.green, other.green)?,
+            blue: checked_add(self.blue, other.blue)?,
+        })
+    }
+
+    pub fn checked_sum<'a>(
+        draws: impl IntoIterator<Item = &'a Draw>,
+    ) -> Result<Draw, OverflowError> {
+        draws
+            .into_iter()
+            .try_fold(Draw::default(), |acc, x| acc.checked_add(x))
+    }
+
+    /// Elementwise difference, stopping at zero for colors where `other` is larger.
+    pub fn saturating_sub(&self, other: &Draw) -> Draw {
+        Draw {
+            red: self.red.saturating_sub(other.red),
+            green: self.green.saturating_sub(other.green),
+            blue: self.blue.saturating_sub(other.blue),
+        }
+    }
+}
+
+/// Parses a bag written as `red,green,blue`, e.g. `12,13,14`.
+impl FromStr for Draw {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Draw, String> {
+        let counts = s
+            .split(',')
+            .map(|count| {
+                count
+                    .trim()
+                    .parse::<u64>()
+                    .map_err(|_| format!("unable to parse count: {}", count))
+            })
+            .collect::<Result<Vec<u64>, String>>()?;
+        match counts[..] {
+            [red, green, blue] => Ok(Draw { red, green, blue }),
+            _ => Err(format!("expected red,green,blue but got: {}", s)),
+        }
+    }
+}
+
+/// Dominance order: a draw is less than another if no color has a larger count.
+impl PartialOrd for Draw {
+    fn partial_cmp(&self, other: &Draw) -> Option<Ordering> {
+        let colors = [
+            self.red.cmp(&other.red),
+            self.green.cmp(&other.green),
+            self.blue.cmp(&other.blue),
+        ];
+        if colors.iter().all(|ord| *ord == Ordering::Equal) {
+            Some(Ordering::Equal)
+        } else if colors.iter().all(|ord| *ord != Ordering::Greater) {
+            Some(Ordering::Less)
+        } else if colors.iter().all(|ord| *ord != Ordering::Less) {
+            Some(Ordering::Greater)
+        } else {
+            None
+        }
+    }
+}
+
+/// Collecting draws joins them, producing the smallest bag able to produce all of them.
+impl FromIterator<Draw> for Draw {
+    fn from_iter<I: IntoIterator<Item = Draw>>(iter: I) -> Draw {
+        iter.into_iter()
+            .fold(Draw::default(), |acc, x| acc.join(&x))
+    }
+}
+
+impl<'a> FromIterator<&'a Draw> for Draw {
+    fn from_iter<I: IntoIterator<Item = &'a Draw>>(iter: I) -> Draw {
+        iter.into_iter().copied().collect()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Game {
+    pub id: u64,
+    pub draws: Vec<Draw>,
+}
+
+impl Game {
+    pub fn from_line(line: String) -> Result<Game, ParseError> {
+        let cubes = pair(ws(unsigned()), whitespace(), ws(word()));
+        let draw = map(separated(cubes, literal(",")), |cubes| {
+            let colors = cubes
+                .into_iter()
+                .map(|(count, color)| (color, count))
+                .collect::<HashMap<&str, u64>>();
+            Draw {
+                red: *colors.get("red").unwrap_or(&0),
+                green: *colors.get("green").unwrap_or(&0),
+                blue: *colors.get("blue").unwrap_or(&0),
+            }
+        });
+        let game = pair(
+            pair(literal("Game"), whitespace(), unsigned()),
+            literal(":"),
+            separated(draw, literal(";")),
+        );
+
+        let ((_, id), draws) = parse_all(game, &line)?;
+        Ok(Game { id, draws })
+    }
+}
+
+/// Lazily parse the games in `input`, one line at a time.
+pub fn parse_games<R: BufRead>(input: R) -> impl Iterator<Item = Result<Game, GameError>> {
+    input
+        .lines()
+        .filter(|line| line.as_ref().map_or(true, |line| !line.is_empty()))
+        .map(|line| match line {
+            Ok(line) => Ok(Game::from_line(line)?),
+            Err(err) => Err(GameError::Read(err.to_string())),
+        })
+}
+
+pub fn read_games(path: &str) -> Result<Vec<Game>, ParseError> {
+    std::fs::read_to_string(path)
+        .map(|file| {
+            file.lines()
+                .filter(|line| !line.is_empty())
+                .map(|val| Game::from_line(val.to_string()))
+                .collect::<Result<Vec<Game>, ParseError>>()
+        })
+        .expect("Unable to open file")
+}
+
+pub fn part1(input: &str) -> Answer {
+    Ok(eval::evaluate(input.as_bytes(), PUZZLE_BAG)?
+        .possible_id_sum
+        .to_string())
+}
+
+pub fn part2(input: &str) -> Answer {
+    Ok(eval::evaluate(input.as_bytes(), PUZZLE_BAG)?
+        .total_power
+        .to_string())
+}
+
+/// The example game log from the puzzle description, for tests.
+#[cfg(test)]
+pub(crate) const EXAMPLE: &str = include_str!("../fixtures/day02/example.txt");
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse::ParseErrorKind;
+
+    #[test]
+    fn test_game_from_line() {
+        assert_eq!(
+            Game::from_line("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green".to_string())
+                .unwrap(),
+            Game {
+                id: 1,
+                draws: vec![
+                    Draw {
+                        red: 4,
+                        green: 0,
+                        blue: 3,
+                    },
+                    Draw {
+                        red: 1,
+                        green: 2,
+                        blue: 6,
+                    },
+                    Draw {
+                        red: 0,
+                        green: 2,
+                        blue: 0,
+                    },
+                ]
+            },
+        )
+    }
+
+    #[test]
+    fn test_is_possible_game_true() {
+        assert!(is_possible_game(
+            &Game {
+                id: 1,
+                draws: vec![
+                    Draw {
+                        red: 4,
+                        green: 0,
+                        blue: 3,
+                    },
+                    Draw {
+                        red: 1,
+                        green: 2,
+                        blue: 6,
+                    },
+                    Draw {
+                        red: 0,
+                        green: 2,
+                        blue: 0,
+                    },
+                ]
+            },
+            &Draw {
+                red: 12,
+                green: 13,
+                blue: 14
+            }
+        ))
+    }
+
+    #[test]
+    fn test_is_possible_game_false() {
+        assert!(!is_possible_game(
+            &Game {
+                id: 1,
+                draws: vec![
+                    Draw {
+                        red: 20,
+                        green: 8,
+                        blue: 6,
+                    },
+                    Draw {
+                        red: 4,
+                        green: 13,
+                        blue: 5,
+                    },
+                    Draw {
+                        red: 1,
+                        green: 5,
+                        blue: 0,
+                    },
+                ]
+            },
+            &Draw {
+                red: 12,
+                green: 13,
+                blue: 14
+            }
+        ))
+    }
+
+    #[test]
+    fn test_get_smallest_bag() {
+        assert_eq!(
+            get_smallest_bag(&Game {
+                id: 1,
+                draws: vec![
+                    Draw {
+                        red: 4,
+                        green: 0,
+                        blue: 3,
+                    },
+                    Draw {
+                        red: 1,
+                        green: 2,
+                        blue: 6,
+                    },
+                    Draw {
+                        red: 0,
+                        green: 2,
+                        blue: 0,
+                    },
+                ]
+            },),
+            Draw {
+                red: 4,
+                green: 2,
+                blue: 6
+            },
+        )
+    }
+
+    #[test]
+    fn test_draw_join_meet() {
+        let a = Draw {
+            red: 4,
+            green: 0,
+            blue: 3,
+        };
+        let b = Draw {
+            red: 1,
+            green: 2,
+            blue: 6,
+        };
+        assert_eq!(
+            a.join(&b),
+            Draw {
+                red: 4,
+                green: 2,
+                blue: 6
+            }
+        );
+        assert_eq!(
+            a.meet(&b),
+            Draw {
+                red: 1,
+                green: 0,
+                blue: 3
+            }
+        );
+    }
+
+    #[test]
+    fn test_draw_partial_cmp() {
+        let small = Draw {
+            red: 1,
+            green: 2,
+            blue: 3,
+        };
+        let large = Draw {
+            red: 1,
+            green: 5,
+            blue: 3,
+        };
+        let other = Draw {
+            red: 2,
+            green: 0,
+            blue: 0,
+        };
+        assert_eq!(small.partial_cmp(&small), Some(Ordering::Equal));
+        assert!(small < large);
+        assert!(large > small);
+        assert_eq!(small.partial_cmp(&other), None);
+    }
+
+    #[test]
+    fn test_draw_from_str() {
+        assert_eq!("12,13,14".parse::<Draw>(), Ok(PUZZLE_BAG));
+        assert!("12,13".parse::<Draw>().is_err());
+        assert!("12,x,14".parse::<Draw>().is_err());
+    }
+
+    #[test]
+    fn test_draw_sum() {
+        assert_eq!(
+            [
+                Draw {
+                    red: 4,
+                    green: 0,
+                    blue: 3,
+                },
+                Draw {
+                    red: 1,
+                    green: 2,
+                    blue: 6,
+                },
+            ]
+            .iter()
+            .try_fold(Draw::default(), |acc, x| acc.checked_add(x)),
+            Ok(Draw {
+                red: 5,
+                green: 2,
+                blue: 9
+            })
+        )
+    }
+
+    #[test]
+    fn test_get_power() {
+        assert_eq!(
+            get_power(&Draw {
+                red: 4,
+                green: 2,
+                blue: 6
+            }),
+            Ok(48)
+        )
+    }
+
+    #[test]
+    fn test_game_from_line_wide_counts() {
+        assert_eq!(
+            Game::from_line(
+                "Game 65536: 65536 red, 4294967296 green, 18446744073709551615 blue".to_string()
+            )
+            .unwrap(),
+            Game {
+                id: 65536,
+                draws: vec![Draw {
+                    red: 65536,
+                    green: 4294967296,
+                    blue: u64::MAX,
+                }]
+            }
+        )
+    }
+
+    #[test]
+    fn test_game_from_line_count_overflow() {
+        let err = Game::from_line("Game 1: 18446744073709551616 red".to_string()).unwrap_err();
+        assert_eq!(
+            err.kind,
+            ParseErrorKind::Overflow(OverflowError::Parse("18446744073709551616".to_string()))
+        );
+        assert_eq!(
+            err.to_string(),
+            "number too large: 18446744073709551616 at column 8"
+        );
+    }
+
+    #[test]
+    fn test_game_from_line_missing_colon() {
+        assert_eq!(
+            Game::from_line("Game 1 3 red".to_string())
+                .unwrap_err()
+                .to_string(),
+            "expected \":\" at column 6 but found \" 3 red\""
+        );
+    }
+
+    #[test]
+    fn test_get_power_edges() {
+        assert_eq!(
+            get_power(&Draw {
+                red: u16::MAX as u64,
+                green: u16::MAX as u64,
+                blue: u16::MAX as u64,
+            }),
+            Ok(281462092005375)
+        );
+        assert_eq!(
+            get_power(&Draw {
+                red: 1 << 32,
+                green: (1 << 32) - 1,
+                blue: 1,
+            }),
+            Ok(u64::MAX - u32::MAX as u64)
+        );
+        assert_eq!(
+            get_power(&Draw {
+                red: 1 << 32,
+                green: 1 << 32,
+                blue: 1,
+            }),
+            Err(OverflowError::Mul(1 << 32, 1 << 32))
+        );
+    }
+
+    #[test]
+    fn test_draw_checked_sum() {
+        let max = Draw {
+            red: u64::MAX,
+            green: 0,
+            blue: 0,
+        };
+        let one = Draw {
+            red: 1,
+            green: 0,
+            blue: 0,
+        };
+        assert_eq!(Draw::checked_sum([&max]), Ok(max));
+        assert_eq!(
+            Draw::checked_sum([&max, &one]),
+            Err(OverflowError::Add(u64::MAX, 1))
+        );
+    }
+}
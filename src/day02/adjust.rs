@@ -0,0 +1,133 @@
+use super::{get_smallest_bag, is_possible_game, Draw, Game};
+
+/// How much each color in a bag would have to grow for `game` to become possible.
+#[derive(Debug, PartialEq)]
+pub struct Growth {
+    pub id: u64,
+    pub growth: Draw,
+}
+
+/// The growth required for every game that is not possible with `bag`.
+pub fn get_required_growth<'a>(
+    games: impl IntoIterator<Item = &'a Game>,
+    bag: &Draw,
+) -> Vec<Growth> {
+    games
+        .into_iter()
+        .filter(|game| !is_possible_game(game, bag))
+        .map(|game| Growth {
+            id: game.id,
+            growth: get_smallest_bag(game).saturating_sub(bag),
+        })
+        .collect()
+}
+
+/// The largest amount that can be removed from each color of `bag` while every one of `games`
+/// stays possible, or the IDs of the chosen games that are not possible to begin with.
+pub fn get_max_reduction<'a>(
+    games: impl IntoIterator<Item = &'a Game>,
+    bag: &Draw,
+) -> Result<Draw, Vec<u64>> {
+    let games = games.into_iter().collect::<Vec<&Game>>();
+    let impossible = games
+        .iter()
+        .filter(|game| !is_possible_game(game, bag))
+        .map(|game| game.id)
+        .collect::<Vec<u64>>();
+    if !impossible.is_empty() {
+        return Err(impossible);
+    }
+    let needed = games
+        .iter()
+        .map(|game| get_smallest_bag(game))
+        .collect::<Draw>();
+    Ok(bag.saturating_sub(&needed))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day02::EXAMPLE;
+    use crate::day02::PUZZLE_BAG;
+
+    fn games() -> Vec<Game> {
+        EXAMPLE
+            .lines()
+            .map(|line| Game::from_line(line.to_string()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_get_required_growth() {
+        let games = games();
+        let growth = get_required_growth(&games, &PUZZLE_BAG);
+        assert_eq!(
+            growth,
+            vec![
+                Growth {
+                    id: 3,
+                    growth: Draw {
+                        red: 8,
+                        green: 0,
+                        blue: 0
+                    }
+                },
+                Growth {
+                    id: 4,
+                    growth: Draw {
+                        red: 2,
+                        green: 0,
+                        blue: 1
+                    }
+                },
+            ]
+        );
+        growth.iter().for_each(|growth| {
+            assert!(is_possible_game(
+                &games[growth.id as usize - 1],
+                &PUZZLE_BAG.checked_add(&growth.growth).unwrap()
+            ))
+        });
+    }
+
+    #[test]
+    fn test_get_max_reduction() {
+        let games = games();
+        let chosen = [&games[0], &games[1], &games[4]];
+        let reduction = get_max_reduction(chosen, &PUZZLE_BAG).unwrap();
+        assert_eq!(
+            reduction,
+            Draw {
+                red: 6,
+                green: 10,
+                blue: 8
+            }
+        );
+        let reduced = PUZZLE_BAG.saturating_sub(&reduction);
+        assert!(chosen.iter().all(|game| is_possible_game(game, &reduced)));
+        [
+            Draw {
+                red: 1,
+                ..Draw::default()
+            },
+            Draw {
+                green: 1,
+                ..Draw::default()
+            },
+            Draw {
+                blue: 1,
+                ..Draw::default()
+            },
+        ]
+        .iter()
+        .for_each(|extra| {
+            let reduced = PUZZLE_BAG.saturating_sub(&reduction.checked_add(extra).unwrap());
+            assert!(!chosen.iter().all(|game| is_possible_game(game, &reduced)));
+        });
+    }
+
+    #[test]
+    fn test_get_max_reduction_impossible() {
+        assert_eq!(get_max_reduction(&games(), &PUZZLE_BAG), Err(vec![3, 4]));
+    }
+}
@@ -0,0 +1,101 @@
+use std::io::BufRead;
+
+use crate::arith::{checked_add, OverflowError};
+
+use super::{get_power, get_smallest_bag, is_possible_game, parse_games, Draw, Game, GameError};
+
+/// Answers to both parts of the puzzle.
+#[derive(Debug, PartialEq, Default)]
+pub struct Answers {
+    pub possible_id_sum: u64,
+    pub total_power: u64,
+}
+
+/// Accumulates both answers one game at a time, so a log never has to be held in memory.
+#[derive(Debug)]
+pub struct Evaluator {
+    bag: Draw,
+    answers: Answers,
+}
+
+impl Evaluator {
+    pub fn new(bag: Draw) -> Evaluator {
+        Evaluator {
+            bag,
+            answers: Answers::default(),
+        }
+    }
+
+    pub fn push(&mut self, game: &Game) -> Result<(), OverflowError> {
+        if is_possible_game(game, &self.bag) {
+            self.answers.possible_id_sum = checked_add(self.answers.possible_id_sum, game.id)?;
+        }
+        self.answers.total_power = checked_add(
+            self.answers.total_power,
+            get_power(&get_smallest_bag(game))?,
+        )?;
+        Ok(())
+    }
+
+    pub fn finish(self) -> Answers {
+        self.answers
+    }
+}
+
+/// Parse and evaluate every game in `input` in a single pass.
+pub fn evaluate<R: BufRead>(input: R, bag: Draw) -> Result<Answers, GameError> {
+    let mut evaluator = Evaluator::new(bag);
+    for game in parse_games(input) {
+        evaluator.push(&game?)?;
+    }
+    Ok(evaluator.finish())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day02::EXAMPLE;
+    use crate::day02::PUZZLE_BAG;
+
+    #[test]
+    fn test_evaluate() {
+        assert_eq!(
+            evaluate(EXAMPLE.as_bytes(), PUZZLE_BAG,),
+            Ok(Answers {
+                possible_id_sum: 8,
+                total_power: 2286,
+            })
+        )
+    }
+
+    #[test]
+    fn test_evaluate_overflow() {
+        assert_eq!(
+            evaluate(
+                "Game 1: 4294967296 red, 4294967296 green, 1 blue".as_bytes(),
+                PUZZLE_BAG
+            ),
+            Err(GameError::Overflow(OverflowError::Mul(
+                4294967296, 4294967296
+            )))
+        );
+        assert_eq!(
+            evaluate(
+                "Game 18446744073709551615: 1 red
+Game 1: 1 red"
+                    .as_bytes(),
+                PUZZLE_BAG
+            ),
+            Err(GameError::Overflow(OverflowError::Add(u64::MAX, 1)))
+        );
+    }
+
+    #[test]
+    fn test_evaluate_unreadable() {
+        assert_eq!(
+            evaluate(&b"Game 1: 3 blue\nGame 2: \xff red\n"[..], PUZZLE_BAG)
+                .map_err(|err| err.to_string()),
+            Err("unable to read line: stream did not contain valid UTF-8".to_string())
+        );
+    }
+}
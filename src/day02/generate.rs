@@ -0,0 +1,266 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use super::eval::Answers;
+use super::Draw;
+use crate::arith::{checked_add, checked_product, OverflowError};
+use crate::rng::Rng;
+
+/// Shape of a generated game log.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratorConfig {
+    pub seed: u64,
+    pub games: u64,
+    pub min_draws: usize,
+    pub max_draws: usize,
+    /// Colors that may appear in a draw; each must be one of `red`, `green` or `blue`, at most
+    /// once.
+    pub colors: Vec<String>,
+    pub max_count: u64,
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> GeneratorConfig {
+        GeneratorConfig {
+            seed: 0,
+            games: 100,
+            min_draws: 1,
+            max_draws: 6,
+            colors: vec!["red".to_string(), "green".to_string(), "blue".to_string()],
+            max_count: 20,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum GeneratorError {
+    InvalidConfig(String),
+    Overflow(OverflowError),
+}
+
+impl From<OverflowError> for GeneratorError {
+    fn from(err: OverflowError) -> GeneratorError {
+        GeneratorError::Overflow(err)
+    }
+}
+
+impl fmt::Display for GeneratorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeneratorError::InvalidConfig(err) => write!(f, "invalid generator config: {}", err),
+            GeneratorError::Overflow(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for GeneratorError {}
+
+/// A generated log together with the answers expected for a given bag.
+#[derive(Debug, PartialEq)]
+pub struct GeneratedLog {
+    pub log: String,
+    pub answers: Answers,
+}
+
+/// Generate a game log and compute its answers directly from the generated counts, without
+/// going through the parser or solver.
+pub fn generate(config: &GeneratorConfig, bag: &Draw) -> Result<GeneratedLog, GeneratorError> {
+    if config.min_draws < 1 || config.min_draws > config.max_draws {
+        return Err(GeneratorError::InvalidConfig(format!(
+            "invalid draw range {}..={}",
+            config.min_draws, config.max_draws
+        )));
+    }
+    if config.max_count < 1 {
+        return Err(GeneratorError::InvalidConfig(
+            "max count must be at least 1".to_string(),
+        ));
+    }
+    if config.colors.is_empty() {
+        return Err(GeneratorError::InvalidConfig(
+            "at least one color is required".to_string(),
+        ));
+    }
+    let bag_counts = hashmap! {
+        "red" => bag.red,
+        "green" => bag.green,
+        "blue" => bag.blue,
+    };
+    let mut seen = HashSet::new();
+    for color in &config.colors {
+        if !bag_counts.contains_key(color.as_str()) {
+            return Err(GeneratorError::InvalidConfig(format!(
+                "unsupported color: {}",
+                color
+            )));
+        }
+        if !seen.insert(color) {
+            return Err(GeneratorError::InvalidConfig(format!(
+                "duplicate color: {}",
+                color
+            )));
+        }
+    }
+
+    let mut rng = Rng::new(config.seed);
+    let mut log = String::new();
+    let mut answers = Answers::default();
+    for id in 1..=config.games {
+        let num_draws = rng.range(config.min_draws as u64, config.max_draws as u64);
+        let mut maxes: HashMap<&str, u64> = HashMap::new();
+        let draws = (0..num_draws)
+            .map(|_| {
+                let mut colors = config
+                    .colors
+                    .iter()
+                    .map(|color| color.as_str())
+                    .collect::<Vec<&str>>();
+                rng.shuffle(&mut colors);
+                let num_colors = rng.range(1, colors.len() as u64) as usize;
+                colors[..num_colors]
+                    .iter()
+                    .map(|color| {
+                        let count = rng.range(1, config.max_count);
+                        let max = maxes.entry(color).or_insert(0);
+                        *max = (*max).max(count);
+                        format!("{} {}", count, color)
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            })
+            .collect::<Vec<String>>()
+            .join("; ");
+        log.push_str(&format!("Game {}: {}\n", id, draws));
+
+        if maxes.iter().all(|(color, max)| *max <= bag_counts[color]) {
+            answers.possible_id_sum = checked_add(answers.possible_id_sum, id)?;
+        }
+        answers.total_power = checked_add(
+            answers.total_power,
+            checked_product(
+                ["red", "green", "blue"]
+                    .iter()
+                    .map(|color| maxes.get(color).copied().unwrap_or(0)),
+            )?,
+        )?;
+    }
+
+    Ok(GeneratedLog { log, answers })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day02::eval::evaluate;
+    use crate::day02::{Game, PUZZLE_BAG};
+
+    #[test]
+    fn test_generate_deterministic() {
+        let config = GeneratorConfig {
+            seed: 17,
+            ..GeneratorConfig::default()
+        };
+        assert_eq!(
+            generate(&config, &PUZZLE_BAG).unwrap(),
+            generate(&config, &PUZZLE_BAG).unwrap()
+        );
+        assert_ne!(
+            generate(&config, &PUZZLE_BAG).unwrap().log,
+            generate(
+                &GeneratorConfig {
+                    seed: 18,
+                    ..config.clone()
+                },
+                &PUZZLE_BAG
+            )
+            .unwrap()
+            .log
+        );
+    }
+
+    #[test]
+    fn test_generate_respects_config() {
+        let config = GeneratorConfig {
+            seed: 3,
+            games: 50,
+            min_draws: 2,
+            max_draws: 4,
+            colors: vec!["red".to_string(), "blue".to_string()],
+            max_count: 7,
+        };
+        let games = generate(&config, &PUZZLE_BAG)
+            .unwrap()
+            .log
+            .lines()
+            .map(|line| Game::from_line(line.to_string()).unwrap())
+            .collect::<Vec<Game>>();
+        assert_eq!(games.len(), 50);
+        assert!(games
+            .iter()
+            .enumerate()
+            .all(|(i, game)| game.id as usize == i + 1));
+        assert!(games.iter().all(|game| (2..=4).contains(&game.draws.len())));
+        assert!(games.iter().flat_map(|game| game.draws.iter()).all(|draw| {
+            draw.green == 0 && draw.red <= 7 && draw.blue <= 7 && draw.red + draw.blue > 0
+        }));
+    }
+
+    #[test]
+    fn test_generate_invalid_config() {
+        let invalid = |config: GeneratorConfig| generate(&config, &PUZZLE_BAG).unwrap_err();
+        assert_eq!(
+            invalid(GeneratorConfig {
+                colors: vec!["red".to_string(), "red".to_string()],
+                ..GeneratorConfig::default()
+            }),
+            GeneratorError::InvalidConfig("duplicate color: red".to_string())
+        );
+        assert_eq!(
+            invalid(GeneratorConfig {
+                colors: vec!["teal".to_string()],
+                ..GeneratorConfig::default()
+            }),
+            GeneratorError::InvalidConfig("unsupported color: teal".to_string())
+        );
+        assert_eq!(
+            invalid(GeneratorConfig {
+                min_draws: 3,
+                max_draws: 2,
+                ..GeneratorConfig::default()
+            }),
+            GeneratorError::InvalidConfig("invalid draw range 3..=2".to_string())
+        );
+        assert_eq!(
+            invalid(GeneratorConfig {
+                max_count: 0,
+                ..GeneratorConfig::default()
+            }),
+            GeneratorError::InvalidConfig("max count must be at least 1".to_string())
+        );
+        assert_eq!(
+            invalid(GeneratorConfig {
+                colors: vec![],
+                ..GeneratorConfig::default()
+            }),
+            GeneratorError::InvalidConfig("at least one color is required".to_string())
+        );
+    }
+
+    #[test]
+    fn test_generate_matches_solver() {
+        (0..20).for_each(|seed| {
+            let generated = generate(
+                &GeneratorConfig {
+                    seed,
+                    ..GeneratorConfig::default()
+                },
+                &PUZZLE_BAG,
+            )
+            .unwrap();
+            assert_eq!(
+                evaluate(generated.log.as_bytes(), PUZZLE_BAG),
+                Ok(generated.answers)
+            );
+        })
+    }
+}
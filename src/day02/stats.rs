@@ -0,0 +1,192 @@
+use std::collections::BTreeMap;
+
+use super::{get_power, get_smallest_bag, Game};
+use crate::arith::OverflowError;
+
+/// Min, max, mean and histogram of a series of observations.
+#[derive(Debug, PartialEq)]
+pub struct Summary {
+    pub min: u64,
+    pub max: u64,
+    pub mean: f64,
+    pub histogram: BTreeMap<u64, usize>,
+}
+
+impl Summary {
+    pub fn from_values(values: impl IntoIterator<Item = u64>) -> Summary {
+        let mut histogram: BTreeMap<u64, usize> = BTreeMap::new();
+        // widened so that the total of any number of u64 values fits
+        let mut total = 0u128;
+        let mut count = 0usize;
+        for val in values {
+            *histogram.entry(val).or_insert(0) += 1;
+            total += val as u128;
+            count += 1;
+        }
+        Summary {
+            min: histogram.keys().next().copied().unwrap_or(0),
+            max: histogram.keys().next_back().copied().unwrap_or(0),
+            mean: if count == 0 {
+                0.0
+            } else {
+                total as f64 / count as f64
+            },
+            histogram,
+        }
+    }
+}
+
+/// Aggregate statistics over every game in a log.
+#[derive(Debug, PartialEq)]
+pub struct GameStats {
+    pub games: usize,
+    pub red: Summary,
+    pub green: Summary,
+    pub blue: Summary,
+    pub draws_per_game: Summary,
+    pub power: Summary,
+}
+
+impl GameStats {
+    pub fn from_games(games: &[Game]) -> Result<GameStats, OverflowError> {
+        let draws = || games.iter().flat_map(|game| game.draws.iter());
+        let powers = games
+            .iter()
+            .map(|game| get_power(&get_smallest_bag(game)))
+            .collect::<Result<Vec<u64>, OverflowError>>()?;
+        Ok(GameStats {
+            games: games.len(),
+            red: Summary::from_values(draws().map(|draw| draw.red)),
+            green: Summary::from_values(draws().map(|draw| draw.green)),
+            blue: Summary::from_values(draws().map(|draw| draw.blue)),
+            draws_per_game: Summary::from_values(games.iter().map(|game| game.draws.len() as u64)),
+            power: Summary::from_values(powers),
+        })
+    }
+
+    fn metrics(&self) -> [(&'static str, &Summary); 5] {
+        [
+            ("red", &self.red),
+            ("green", &self.green),
+            ("blue", &self.blue),
+            ("draws", &self.draws_per_game),
+            ("power", &self.power),
+        ]
+    }
+
+    pub fn to_table(&self) -> String {
+        let mut out = format!("games: {}\n", self.games);
+        out.push_str(&format!(
+            "{:<8}{:>10}{:>10}{:>12}\n",
+            "metric", "min", "max", "mean"
+        ));
+        for (name, summary) in self.metrics() {
+            out.push_str(&format!(
+                "{:<8}{:>10}{:>10}{:>12.3}\n",
+                name, summary.min, summary.max, summary.mean
+            ));
+        }
+        for (name, summary) in self.metrics() {
+            out.push_str(&format!("\n{} histogram\n", name));
+            for (val, count) in &summary.histogram {
+                out.push_str(&format!("{:>10}{:>10}\n", val, count));
+            }
+        }
+        out
+    }
+
+    /// Long-format CSV: one row per summary value and one row per histogram bucket.
+    pub fn to_csv(&self) -> String {
+        let mut out = "metric,kind,key,value\n".to_string();
+        for (name, summary) in self.metrics() {
+            out.push_str(&format!("{},min,,{}\n", name, summary.min));
+            out.push_str(&format!("{},max,,{}\n", name, summary.max));
+            out.push_str(&format!("{},mean,,{}\n", name, summary.mean));
+            for (val, count) in &summary.histogram {
+                out.push_str(&format!("{},histogram,{},{}\n", name, val, count));
+            }
+        }
+        out
+    }
+
+    pub fn to_json(&self) -> String {
+        let metrics = self
+            .metrics()
+            .iter()
+            .map(|(name, summary)| {
+                let histogram = summary
+                    .histogram
+                    .iter()
+                    .map(|(val, count)| format!("\"{}\":{}", val, count))
+                    .collect::<Vec<String>>()
+                    .join(",");
+                format!(
+                    "\"{}\":{{\"min\":{},\"max\":{},\"mean\":{},\"histogram\":{{{}}}}}",
+                    name, summary.min, summary.max, summary.mean, histogram
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(",");
+        format!("{{\"games\":{},{}}}", self.games, metrics)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use maplit::btreemap;
+
+    fn games() -> Vec<Game> {
+        vec![
+            Game::from_line("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green".to_string())
+                .unwrap(),
+            Game::from_line("Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red".to_string()).unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_summary_from_values() {
+        assert_eq!(
+            Summary::from_values(vec![3, 1, 3, 5]),
+            Summary {
+                min: 1,
+                max: 5,
+                mean: 3.0,
+                histogram: btreemap! {1 => 1, 3 => 2, 5 => 1},
+            }
+        )
+    }
+
+    #[test]
+    fn test_summary_from_values_wide() {
+        let summary = Summary::from_values(vec![u64::MAX, u64::MAX]);
+        assert_eq!(summary.max, u64::MAX);
+        assert_eq!(summary.mean, u64::MAX as f64);
+    }
+
+    #[test]
+    fn test_game_stats_from_games() {
+        let stats = GameStats::from_games(&games()).unwrap();
+        assert_eq!(stats.games, 2);
+        assert_eq!(stats.red.histogram, btreemap! {0 => 2, 1 => 2, 4 => 1});
+        assert_eq!(stats.draws_per_game.histogram, btreemap! {2 => 1, 3 => 1});
+        assert_eq!(stats.power.histogram, btreemap! {12 => 1, 48 => 1});
+        assert_eq!(stats.power.mean, 30.0);
+    }
+
+    #[test]
+    fn test_game_stats_to_csv() {
+        let csv = GameStats::from_games(&games()).unwrap().to_csv();
+        assert!(csv.starts_with("metric,kind,key,value\nred,min,,0\nred,max,,4\n"));
+        assert!(csv.contains("\npower,histogram,48,1\n"));
+    }
+
+    #[test]
+    fn test_game_stats_to_json() {
+        let json = GameStats::from_games(&games()).unwrap().to_json();
+        assert!(json.starts_with("{\"games\":2,\"red\":{\"min\":0,\"max\":4,\"mean\":1.2,"));
+        assert!(json.ends_with(
+            "\"power\":{\"min\":12,\"max\":48,\"mean\":30,\"histogram\":{\"12\":1,\"48\":1}}}"
+        ));
+    }
+}
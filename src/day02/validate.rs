@@ -0,0 +1,189 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use super::Game;
+
+/// A problem with the game IDs of a log. Positions are 1-based indexes of games in the log.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum IdIssue {
+    Duplicate {
+        id: u64,
+        position: usize,
+        first_position: usize,
+    },
+    Missing {
+        from: u64,
+        to: u64,
+    },
+    OutOfOrder {
+        id: u64,
+        position: usize,
+        previous: u64,
+    },
+}
+
+impl fmt::Display for IdIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IdIssue::Duplicate {
+                id,
+                position,
+                first_position,
+            } => write!(
+                f,
+                "duplicate id {} at game {} (first seen at game {})",
+                id, position, first_position
+            ),
+            IdIssue::Missing { from, to } if from == to => write!(f, "missing id {}", from),
+            IdIssue::Missing { from, to } => write!(f, "missing ids {}-{}", from, to),
+            IdIssue::OutOfOrder {
+                id,
+                position,
+                previous,
+            } => write!(f, "id {} at game {} follows id {}", id, position, previous),
+        }
+    }
+}
+
+/// Checks game IDs as they stream past. IDs are expected to count up from 1 with no gaps.
+///
+/// Every ID seen is kept so that duplicates can be reported wherever they appear.
+#[derive(Debug, Default)]
+pub struct IdValidator {
+    seen: BTreeMap<u64, usize>,
+    position: usize,
+    previous: Option<u64>,
+    issues: Vec<IdIssue>,
+}
+
+impl IdValidator {
+    pub fn push(&mut self, id: u64) {
+        self.position += 1;
+        match self.seen.get(&id) {
+            Some(first_position) => self.issues.push(IdIssue::Duplicate {
+                id,
+                position: self.position,
+                first_position: *first_position,
+            }),
+            None => {
+                self.seen.insert(id, self.position);
+            }
+        }
+        if let Some(previous) = self.previous {
+            if id < previous {
+                self.issues.push(IdIssue::OutOfOrder {
+                    id,
+                    position: self.position,
+                    previous,
+                });
+            }
+        }
+        self.previous = Some(id);
+    }
+
+    pub fn finish(mut self) -> Vec<IdIssue> {
+        let mut expected = 1u64;
+        for id in self.seen.keys() {
+            if *id > expected {
+                self.issues.push(IdIssue::Missing {
+                    from: expected,
+                    to: id - 1,
+                });
+            }
+            expected = id.saturating_add(1);
+        }
+        self.issues
+    }
+}
+
+pub fn validate_ids<'a>(games: impl IntoIterator<Item = &'a Game>) -> Vec<IdIssue> {
+    let mut validator = IdValidator::default();
+    games.into_iter().for_each(|game| validator.push(game.id));
+    validator.finish()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn validate(ids: &[u64]) -> Vec<IdIssue> {
+        let mut validator = IdValidator::default();
+        ids.iter().for_each(|id| validator.push(*id));
+        validator.finish()
+    }
+
+    #[test]
+    fn test_validate_ids_consistent() {
+        let games = "Game 1: 3 blue
+Game 2: 1 red
+Game 3: 2 green"
+            .lines()
+            .map(|line| Game::from_line(line.to_string()).unwrap())
+            .collect::<Vec<Game>>();
+        assert_eq!(validate_ids(&games), vec![]);
+        assert_eq!(validate(&[]), vec![]);
+    }
+
+    #[test]
+    fn test_validate_ids_duplicate() {
+        assert_eq!(
+            validate(&[1, 2, 2, 3]),
+            vec![IdIssue::Duplicate {
+                id: 2,
+                position: 3,
+                first_position: 2
+            }]
+        )
+    }
+
+    #[test]
+    fn test_validate_ids_missing() {
+        assert_eq!(
+            validate(&[3, 4, 6, 10]),
+            vec![
+                IdIssue::Missing { from: 1, to: 2 },
+                IdIssue::Missing { from: 5, to: 5 },
+                IdIssue::Missing { from: 7, to: 9 },
+            ]
+        )
+    }
+
+    #[test]
+    fn test_validate_ids_out_of_order() {
+        assert_eq!(
+            validate(&[1, 3, 2, 4, 1]),
+            vec![
+                IdIssue::OutOfOrder {
+                    id: 2,
+                    position: 3,
+                    previous: 3
+                },
+                IdIssue::Duplicate {
+                    id: 1,
+                    position: 5,
+                    first_position: 1
+                },
+                IdIssue::OutOfOrder {
+                    id: 1,
+                    position: 5,
+                    previous: 4
+                },
+            ]
+        )
+    }
+
+    #[test]
+    fn test_id_issue_display() {
+        assert_eq!(
+            validate(&[1, 3, 3, 2, 7])
+                .iter()
+                .map(|issue| issue.to_string())
+                .collect::<Vec<String>>(),
+            vec![
+                "duplicate id 3 at game 3 (first seen at game 2)",
+                "id 2 at game 4 follows id 3",
+                "missing ids 4-6",
+            ]
+        )
+    }
+}
@@ -0,0 +1,844 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::arith::{checked_mul, checked_sum, parse_u64, OverflowError};
+use crate::grid::{parse_grid, read_grid, GridOptions};
+use crate::parse::{find_all, spanned, take_while1};
+use crate::runner::Answer;
+
+pub mod diagnostics;
+pub mod dialect;
+pub mod diff;
+pub mod generate;
+pub mod graph;
+pub mod neighborhood;
+pub mod render;
+pub mod rules;
+pub mod spatial;
+pub mod stream;
+pub mod svg;
+pub mod symbols;
+
+use neighborhood::Neighborhood;
+use symbols::{CellKind, SymbolSet};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SchematicError {
+    UnknownChar { line: usize, col: usize, c: char },
+    InvalidSymbolSet(String),
+    Overflow(OverflowError),
+    DecimalOverflow(String),
+    InvalidDecimal(String),
+    Read(String),
+}
+
+impl From<OverflowError> for SchematicError {
+    fn from(err: OverflowError) -> SchematicError {
+        SchematicError::Overflow(err)
+    }
+}
+
+impl fmt::Display for SchematicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchematicError::UnknownChar { line, col, c } => write!(
+                f,
+                "unknown character {:?} at line {}, column {}",
+                c, line, col
+            ),
+            SchematicError::InvalidSymbolSet(reason) => {
+                write!(f, "invalid symbol set: {}", reason)
+            }
+            SchematicError::Overflow(err) => write!(f, "{}", err),
+            SchematicError::DecimalOverflow(reason) => write!(f, "{}", reason),
+            SchematicError::InvalidDecimal(s) => write!(f, "unable to parse number from {}", s),
+            SchematicError::Read(err) => write!(f, "unable to read line: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for SchematicError {}
+
+/// Read a schematic, normalizing line endings and padding short rows with `.`.
+pub fn read_lines(path: &str) -> Vec<String> {
+    let file = std::fs::File::open(path).expect("Unable to open file");
+    read_grid(file, &GridOptions::default())
+        .unwrap_or_else(|err| panic!("{}", err))
+        .lines
+}
+
+/// A part number and the cells it covers: columns `start..end` of `line`. Numbers read in other
+/// dialects carry their own value type, e.g. [`dialect::Decimal`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct PartSpan<V = u64> {
+    pub line: usize,
+    pub start: usize,
+    pub end: usize,
+    pub value: V,
+}
+
+impl<V> PartSpan<V> {
+    /// Whether the cell at (`line`, `col`) touches the span, including diagonally and without
+    /// wraparound. This is the default neighborhood; see [`PartSpan::touches`] for others.
+    pub fn is_adjacent(&self, line: usize, col: usize) -> bool {
+        self.touches(line, col, &Neighborhood::default())
+    }
+
+    /// Whether any neighbor of the cell at (`line`, `col`) is covered by the span.
+    pub fn touches(&self, line: usize, col: usize, neighborhood: &Neighborhood) -> bool {
+        neighborhood
+            .neighbors(line, col)
+            .iter()
+            .any(|(l, c)| *l == self.line && self.start <= *c && *c < self.end)
+    }
+}
+
+pub fn get_part_spans(
+    lines: &[String],
+    symbol_set: &SymbolSet,
+) -> Result<Vec<PartSpan>, OverflowError> {
+    let mut out = Vec::new();
+    for (line_i, line) in lines.iter().enumerate() {
+        out.extend(get_line_part_spans(line_i, line, symbol_set)?);
+    }
+    Ok(out)
+}
+
+/// The numbers of a single row, which is line `line_i` of the schematic.
+pub fn get_line_part_spans(
+    line_i: usize,
+    line: &str,
+    symbol_set: &SymbolSet,
+) -> Result<Vec<PartSpan>, OverflowError> {
+    let number = spanned(take_while1(
+        |c| symbol_set.classify(c) == Some(CellKind::Digit),
+        "a digit",
+    ));
+    // a run of digits either matches or does not, so the scan itself cannot fail
+    find_all(&number, line)
+        .expect("unable to scan for digits")
+        .into_iter()
+        .map(|(digits, span)| {
+            Ok(PartSpan {
+                line: line_i,
+                start: span.start,
+                end: span.end,
+                value: parse_u64(digits)?,
+            })
+        })
+        .collect()
+}
+
+/// A `*` symbol with exactly two adjacent part numbers.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Gear<V = u64> {
+    pub line: usize,
+    pub col: usize,
+    pub parts: [PartSpan<V>; 2],
+}
+
+impl Gear {
+    pub fn ratio(&self) -> Result<u64, OverflowError> {
+        checked_mul(self.parts[0].value, self.parts[1].value)
+    }
+}
+
+/// Every gear in the schematic, ordered by position.
+pub fn get_gears<V: Copy>(
+    spans: &[PartSpan<V>],
+    symbols: &HashMap<(usize, usize), char>,
+    neighborhood: &Neighborhood,
+) -> Vec<Gear<V>> {
+    let mut out = symbols
+        .iter()
+        .filter(|(_, c)| **c == '*')
+        .filter_map(|((line_i, row_i), _)| {
+            let adjacent = spans
+                .iter()
+                .filter(|span| span.touches(*line_i, *row_i, neighborhood))
+                .collect::<Vec<&PartSpan<V>>>();
+            match adjacent[..] {
+                [a, b] => Some(Gear {
+                    line: *line_i,
+                    col: *row_i,
+                    parts: [*a, *b],
+                }),
+                _ => None,
+            }
+        })
+        .collect::<Vec<Gear<V>>>();
+    out.sort_by_key(|gear| (gear.line, gear.col));
+    out
+}
+
+/// Whether any symbol touches `span`.
+pub fn is_part_number<V>(
+    span: &PartSpan<V>,
+    symbols: &HashMap<(usize, usize), char>,
+    neighborhood: &Neighborhood,
+) -> bool {
+    symbols
+        .keys()
+        .any(|(line_i, row_i)| span.touches(*line_i, *row_i, neighborhood))
+}
+
+pub fn get_part_nums(
+    lines: &[String],
+    symbol_set: &SymbolSet,
+) -> Result<HashMap<(usize, usize), u64>, OverflowError> {
+    Ok(get_part_spans(lines, symbol_set)?
+        .into_iter()
+        .flat_map(|span| (span.start..span.end).map(move |row_i| ((span.line, row_i), span.value)))
+        .collect())
+}
+
+pub fn get_symbols(
+    lines: &[String],
+    symbol_set: &SymbolSet,
+) -> Result<HashMap<(usize, usize), char>, SchematicError> {
+    let mut out = HashMap::new();
+    for (line_i, line) in lines.iter().enumerate() {
+        for (row_i, c) in line.chars().enumerate() {
+            match symbol_set.classify(c) {
+                Some(CellKind::Symbol) => {
+                    out.insert((line_i, row_i), c);
+                }
+                Some(_) => (),
+                None => {
+                    return Err(SchematicError::UnknownChar {
+                        line: line_i,
+                        col: row_i,
+                        c,
+                    })
+                }
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// The part numbers adjacent to each symbol, sorted. A number touching several symbols appears
+/// once for each of them; see [`get_unique_part_numbers`] to count it once.
+pub fn get_adjacent_part_numbers(
+    part_numbers: &HashMap<(usize, usize), u64>,
+    symbols: &HashMap<(usize, usize), char>,
+    neighborhood: &Neighborhood,
+) -> Vec<u64> {
+    let mut out = symbols
+        .keys()
+        .flat_map(|(line_i, row_i)| {
+            get_symbol_part_numbers(part_numbers, *line_i, *row_i, neighborhood)
+        })
+        .collect::<Vec<u64>>();
+    out.sort();
+    out
+}
+
+/// Every part number adjacent to any symbol, sorted. A number touching several symbols is only
+/// counted once.
+pub fn get_unique_part_numbers(
+    part_numbers: &HashMap<(usize, usize), u64>,
+    symbols: &HashMap<(usize, usize), char>,
+    neighborhood: &Neighborhood,
+) -> Vec<u64> {
+    let mut starts = symbols
+        .keys()
+        .flat_map(|(line_i, row_i)| {
+            get_symbol_part_starts(part_numbers, *line_i, *row_i, neighborhood)
+        })
+        .collect::<Vec<(usize, usize)>>();
+    starts.sort();
+    starts.dedup();
+
+    let mut out = starts
+        .iter()
+        .map(|start| part_numbers[start])
+        .collect::<Vec<u64>>();
+    out.sort();
+    out
+}
+
+pub fn get_gear_ratios(
+    part_numbers: &HashMap<(usize, usize), u64>,
+    symbols: &HashMap<(usize, usize), char>,
+    neighborhood: &Neighborhood,
+) -> Result<Vec<u64>, OverflowError> {
+    let mut out = symbols
+        .iter()
+        .filter_map(|((line_i, row_i), c)| {
+            if *c == '*' {
+                let part_numbers =
+                    get_symbol_part_numbers(part_numbers, *line_i, *row_i, neighborhood);
+                if part_numbers.len() == 2 {
+                    Some(checked_mul(part_numbers[0], part_numbers[1]))
+                } else {
+                    None
+                }
+            } else {
+                None
+            }
+        })
+        .collect::<Result<Vec<u64>, OverflowError>>()?;
+    out.sort();
+    Ok(out)
+}
+
+/// The positions of the first digits of the numbers adjacent to the symbol at
+/// (`line_i`, `row_i`), sorted.
+fn get_symbol_part_starts(
+    part_numbers: &HashMap<(usize, usize), u64>,
+    line_i: usize,
+    row_i: usize,
+    neighborhood: &Neighborhood,
+) -> Vec<(usize, usize)> {
+    // numbers are maximal runs of digits, so walking left from any covered cell finds the start
+    // of its number, which identifies it even when several neighbors land on the same number
+    let mut starts = neighborhood
+        .neighbors(line_i, row_i)
+        .into_iter()
+        .filter(|cell| part_numbers.contains_key(cell))
+        .map(|(line, mut col)| {
+            while col > 0 && part_numbers.contains_key(&(line, col - 1)) {
+                col -= 1;
+            }
+            (line, col)
+        })
+        .collect::<Vec<(usize, usize)>>();
+    starts.sort();
+    starts.dedup();
+    starts
+}
+
+/// The part numbers adjacent to the symbol at (`line_i`, `row_i`), sorted.
+pub fn get_symbol_part_numbers(
+    part_numbers: &HashMap<(usize, usize), u64>,
+    line_i: usize,
+    row_i: usize,
+    neighborhood: &Neighborhood,
+) -> Vec<u64> {
+    let mut out = get_symbol_part_starts(part_numbers, line_i, row_i, neighborhood)
+        .iter()
+        .map(|start| part_numbers[start])
+        .collect::<Vec<u64>>();
+    out.sort();
+    out
+}
+
+/// The sum of part numbers and the sum of gear ratios, with any symbol set and neighborhood.
+pub fn solve(
+    lines: &[String],
+    symbol_set: &SymbolSet,
+    neighborhood: &Neighborhood,
+) -> Result<(u64, u64), SchematicError> {
+    let part_nums = get_part_nums(lines, symbol_set)?;
+    let symbols = get_symbols(lines, symbol_set)?;
+    let part_sum = checked_sum(get_unique_part_numbers(&part_nums, &symbols, neighborhood))?;
+    let gear_sum = checked_sum(get_gear_ratios(&part_nums, &symbols, neighborhood)?)?;
+    Ok((part_sum, gear_sum))
+}
+
+pub fn part1(input: &str) -> Answer {
+    let lines = parse_grid(input, &GridOptions::default())?.lines;
+    let (part_sum, _) = solve(&lines, &SymbolSet::default(), &Neighborhood::default())?;
+    Ok(part_sum.to_string())
+}
+
+pub fn part2(input: &str) -> Answer {
+    let lines = parse_grid(input, &GridOptions::default())?.lines;
+    let (_, gear_sum) = solve(&lines, &SymbolSet::default(), &Neighborhood::default())?;
+    Ok(gear_sum.to_string())
+}
+
+/// The example schematic from the puzzle description, for tests.
+#[cfg(test)]
+pub(crate) const EXAMPLE: &str = include_str!("../fixtures/day03/example.txt");
+
+/// [`EXAMPLE`] split into rows.
+#[cfg(test)]
+pub(crate) fn example_lines() -> Vec<String> {
+    EXAMPLE.lines().map(|line| line.to_string()).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use maplit::hashmap;
+
+    #[test]
+    fn test_get_part_nums() {
+        assert_eq!(
+            get_part_nums(&example_lines(), &SymbolSet::default()),
+            Ok(hashmap! {
+                (0, 0) => 467,
+                (0, 1) => 467,
+                (0, 2) => 467,
+                (0, 5) => 114,
+                (0, 6) => 114,
+                (0, 7) => 114,
+                (2, 2) => 35,
+                (2, 3) => 35,
+                (2, 6) => 633,
+                (2, 7) => 633,
+                (2, 8) => 633,
+                (4, 0) => 617,
+                (4, 1) => 617,
+                (4, 2) => 617,
+                (5, 7) => 58,
+                (5, 8) => 58,
+                (6, 2) => 592,
+                (6, 3) => 592,
+                (6, 4) => 592,
+                (7, 6) => 755,
+                (7, 7) => 755,
+                (7, 8) => 755,
+                (9, 1) => 664,
+                (9, 2) => 664,
+                (9, 3) => 664,
+                (9, 5) => 598,
+                (9, 6) => 598,
+                (9, 7) => 598,
+            })
+        )
+    }
+
+    #[test]
+    fn test_get_symbols() {
+        assert_eq!(
+            get_symbols(&example_lines(), &SymbolSet::default()),
+            Ok(hashmap! {
+                (1, 3) => '*',
+                (3, 6) => '#',
+                (4, 3) => '*',
+                (5, 5) => '+',
+                (8, 3) => '$',
+                (8, 5) => '*',
+            }),
+        )
+    }
+
+    #[test]
+    fn test_get_adjacent_part_numbers() {
+        assert_eq!(
+            get_adjacent_part_numbers(
+                &hashmap! {
+                    (0, 0) => 467,
+                    (0, 1) => 467,
+                    (0, 2) => 467,
+                    (0, 5) => 114,
+                    (0, 6) => 114,
+                    (0, 7) => 114,
+                    (2, 2) => 35,
+                    (2, 3) => 35,
+                    (2, 6) => 633,
+                    (2, 7) => 633,
+                    (2, 8) => 633,
+                    (4, 0) => 617,
+                    (4, 1) => 617,
+                    (4, 2) => 617,
+                    (5, 7) => 58,
+                    (5, 8) => 58,
+                    (6, 2) => 592,
+                    (6, 3) => 592,
+                    (6, 4) => 592,
+                    (7, 6) => 755,
+                    (7, 7) => 755,
+                    (7, 8) => 755,
+                    (9, 1) => 664,
+                    (9, 2) => 664,
+                    (9, 3) => 664,
+                    (9, 5) => 598,
+                    (9, 6) => 598,
+                    (9, 7) => 598,
+                },
+                &hashmap! {
+                    (1, 3) => '*',
+                    (3, 6) => '#',
+                    (4, 3) => '*',
+                    (5, 5) => '+',
+                    (8, 3) => '$',
+                    (8, 5) => '*',
+                },
+                &Neighborhood::default(),
+            ),
+            vec![35, 467, 592, 598, 617, 633, 664, 755]
+        )
+    }
+
+    #[test]
+    fn test_get_unique_part_numbers() {
+        let part_numbers = hashmap! {
+            (1, 1) => 12,
+            (1, 2) => 12,
+        };
+        let symbols = hashmap! {
+            (0, 0) => '*',
+            (2, 3) => '#',
+        };
+        assert_eq!(
+            get_adjacent_part_numbers(&part_numbers, &symbols, &Neighborhood::default()),
+            vec![12, 12]
+        );
+        assert_eq!(
+            get_unique_part_numbers(&part_numbers, &symbols, &Neighborhood::default()),
+            vec![12]
+        );
+    }
+
+    #[test]
+    fn test_get_symbol_part_numbers() {
+        assert_eq!(
+            get_symbol_part_numbers(
+                &hashmap! {
+                    (0, 0) => 467,
+                    (0, 1) => 467,
+                    (0, 2) => 467,
+                    (0, 5) => 114,
+                    (0, 6) => 114,
+                    (0, 7) => 114,
+                    (2, 2) => 35,
+                    (2, 3) => 35,
+                    (2, 6) => 633,
+                    (2, 7) => 633,
+                    (2, 8) => 633,
+                    (4, 0) => 617,
+                    (4, 1) => 617,
+                    (4, 2) => 617,
+                    (5, 7) => 58,
+                    (5, 8) => 58,
+                    (6, 2) => 592,
+                    (6, 3) => 592,
+                    (6, 4) => 592,
+                    (7, 6) => 755,
+                    (7, 7) => 755,
+                    (7, 8) => 755,
+                    (9, 1) => 664,
+                    (9, 2) => 664,
+                    (9, 3) => 664,
+                    (9, 5) => 598,
+                    (9, 6) => 598,
+                    (9, 7) => 598,
+                },
+                1,
+                3,
+                &Neighborhood::default(),
+            ),
+            vec![35, 467]
+        )
+    }
+
+    #[test]
+    fn test_get_gear_ratios() {
+        assert_eq!(
+            get_gear_ratios(
+                &hashmap! {
+                    (0, 0) => 467,
+                    (0, 1) => 467,
+                    (0, 2) => 467,
+                    (0, 5) => 114,
+                    (0, 6) => 114,
+                    (0, 7) => 114,
+                    (2, 2) => 35,
+                    (2, 3) => 35,
+                    (2, 6) => 633,
+                    (2, 7) => 633,
+                    (2, 8) => 633,
+                    (4, 0) => 617,
+                    (4, 1) => 617,
+                    (4, 2) => 617,
+                    (5, 7) => 58,
+                    (5, 8) => 58,
+                    (6, 2) => 592,
+                    (6, 3) => 592,
+                    (6, 4) => 592,
+                    (7, 6) => 755,
+                    (7, 7) => 755,
+                    (7, 8) => 755,
+                    (9, 1) => 664,
+                    (9, 2) => 664,
+                    (9, 3) => 664,
+                    (9, 5) => 598,
+                    (9, 6) => 598,
+                    (9, 7) => 598,
+                },
+                &hashmap! {
+                    (1, 3) => '*',
+                    (3, 6) => '#',
+                    (4, 3) => '*',
+                    (5, 5) => '+',
+                    (8, 3) => '$',
+                    (8, 5) => '*',
+                },
+                &Neighborhood::default(),
+            ),
+            Ok(vec![16345, 451490])
+        )
+    }
+
+    #[test]
+    fn test_get_part_nums_wide() {
+        assert_eq!(
+            get_part_nums(
+                &[
+                    "65535.65536".to_string(),
+                    "123456*18446744073709551615".to_string(),
+                ],
+                &SymbolSet::default()
+            ),
+            Ok(hashmap! {
+                (0, 0) => 65535,
+                (0, 1) => 65535,
+                (0, 2) => 65535,
+                (0, 3) => 65535,
+                (0, 4) => 65535,
+                (0, 6) => 65536,
+                (0, 7) => 65536,
+                (0, 8) => 65536,
+                (0, 9) => 65536,
+                (0, 10) => 65536,
+                (1, 0) => 123456,
+                (1, 1) => 123456,
+                (1, 2) => 123456,
+                (1, 3) => 123456,
+                (1, 4) => 123456,
+                (1, 5) => 123456,
+                (1, 7) => u64::MAX,
+                (1, 8) => u64::MAX,
+                (1, 9) => u64::MAX,
+                (1, 10) => u64::MAX,
+                (1, 11) => u64::MAX,
+                (1, 12) => u64::MAX,
+                (1, 13) => u64::MAX,
+                (1, 14) => u64::MAX,
+                (1, 15) => u64::MAX,
+                (1, 16) => u64::MAX,
+                (1, 17) => u64::MAX,
+                (1, 18) => u64::MAX,
+                (1, 19) => u64::MAX,
+                (1, 20) => u64::MAX,
+                (1, 21) => u64::MAX,
+                (1, 22) => u64::MAX,
+                (1, 23) => u64::MAX,
+                (1, 24) => u64::MAX,
+                (1, 25) => u64::MAX,
+                (1, 26) => u64::MAX,
+            })
+        )
+    }
+
+    #[test]
+    fn test_get_part_nums_overflow() {
+        assert_eq!(
+            get_part_nums(
+                &["..18446744073709551616..".to_string()],
+                &SymbolSet::default()
+            ),
+            Err(OverflowError::Parse("18446744073709551616".to_string()))
+        )
+    }
+
+    #[test]
+    fn test_get_gear_ratios_wide() {
+        let lines = vec![
+            "*65536...".to_string(),
+            "65536....".to_string(),
+            "..999999.".to_string(),
+            "........*".to_string(),
+            "...4294967296".to_string(),
+        ];
+        assert_eq!(
+            get_gear_ratios(
+                &get_part_nums(&lines, &SymbolSet::default()).unwrap(),
+                &get_symbols(&lines, &SymbolSet::default()).unwrap(),
+                &Neighborhood::default()
+            ),
+            Ok(vec![4294967296, 4294963001032704])
+        )
+    }
+
+    #[test]
+    fn test_get_gear_ratios_overflow() {
+        let lines = vec![
+            "4294967296.".to_string(),
+            "..........*".to_string(),
+            "..........4294967296".to_string(),
+        ];
+        assert_eq!(
+            get_gear_ratios(
+                &get_part_nums(&lines, &SymbolSet::default()).unwrap(),
+                &get_symbols(&lines, &SymbolSet::default()).unwrap(),
+                &Neighborhood::default()
+            ),
+            Err(OverflowError::Mul(4294967296, 4294967296))
+        )
+    }
+
+    #[test]
+    fn test_part_span_is_adjacent() {
+        let span = PartSpan {
+            line: 2,
+            start: 2,
+            end: 4,
+            value: 35,
+        };
+        assert!(span.is_adjacent(1, 3));
+        assert!(span.is_adjacent(1, 1));
+        assert!(span.is_adjacent(3, 4));
+        assert!(span.is_adjacent(2, 4));
+        assert!(!span.is_adjacent(0, 3));
+        assert!(!span.is_adjacent(2, 0));
+        assert!(!span.is_adjacent(3, 5));
+    }
+
+    #[test]
+    fn test_columns_count_characters() {
+        let lines = vec!["·12*".to_string(), "é3··".to_string()];
+        let symbol_set = SymbolSet::new("*é", "·", "0123456789").unwrap();
+        assert_eq!(
+            get_part_spans(&lines, &symbol_set),
+            Ok(vec![
+                PartSpan {
+                    line: 0,
+                    start: 1,
+                    end: 3,
+                    value: 12
+                },
+                PartSpan {
+                    line: 1,
+                    start: 1,
+                    end: 2,
+                    value: 3
+                },
+            ])
+        );
+        assert_eq!(
+            get_symbols(&lines, &symbol_set),
+            Ok(hashmap! {
+                (0, 3) => '*',
+                (1, 0) => 'é',
+            })
+        );
+    }
+
+    #[test]
+    fn test_solve_non_ascii_glyphs() {
+        let path = std::env::temp_dir().join(format!("day03-glyphs-{}.txt", std::process::id()));
+        std::fs::write(&path, "é12··\n···*·\n····5\n").unwrap();
+        let lines = read_lines(path.to_str().unwrap());
+        std::fs::remove_file(&path).unwrap();
+        let symbol_set = SymbolSet::new("*é", "·", "0123456789").unwrap();
+        // 12 touches both symbols, and 5 shares the `*` with it
+        assert_eq!(
+            solve(&lines, &symbol_set, &Neighborhood::default()),
+            Ok((17, 60))
+        );
+        // the puzzle's own symbol set does not know the glyphs
+        assert!(part1("é12··\n···*·\n····5\n").is_err());
+    }
+
+    #[test]
+    fn test_get_gears() {
+        let lines = example_lines();
+        let gears = get_gears(
+            &get_part_spans(&lines, &SymbolSet::default()).unwrap(),
+            &get_symbols(&lines, &SymbolSet::default()).unwrap(),
+            &Neighborhood::default(),
+        );
+        assert_eq!(
+            gears
+                .iter()
+                .map(|gear| (gear.line, gear.col, gear.ratio()))
+                .collect::<Vec<(usize, usize, Result<u64, OverflowError>)>>(),
+            vec![(1, 3, Ok(16345)), (8, 5, Ok(451490))]
+        );
+    }
+
+    #[test]
+    fn test_part_span_touches() {
+        let span = PartSpan {
+            line: 2,
+            start: 2,
+            end: 4,
+            value: 35,
+        };
+        assert!(span.touches(1, 3, &Neighborhood::four_way()));
+        assert!(!span.touches(1, 1, &Neighborhood::four_way()));
+        assert!(span.touches(0, 5, &Neighborhood::radius(2)));
+        assert!(!span.touches(0, 6, &Neighborhood::radius(2)));
+        assert!(!span.touches(0, 3, &Neighborhood::four_way()));
+        assert!(span.touches(0, 3, &Neighborhood::four_way().with_wraparound(3, 10)));
+        (0..6).for_each(|line| {
+            (0..7).for_each(|col| {
+                assert_eq!(
+                    span.is_adjacent(line, col),
+                    span.touches(line, col, &Neighborhood::eight_way())
+                )
+            })
+        });
+    }
+
+    #[test]
+    fn test_get_symbol_part_numbers_neighborhoods() {
+        let lines = vec![
+            "12...345".to_string(),
+            "...*....".to_string(),
+            "..67....".to_string(),
+            "9.......".to_string(),
+        ];
+        let part_numbers = get_part_nums(&lines, &SymbolSet::default()).unwrap();
+        assert_eq!(
+            get_symbol_part_numbers(&part_numbers, 1, 3, &Neighborhood::four_way()),
+            vec![67]
+        );
+        assert_eq!(
+            get_symbol_part_numbers(&part_numbers, 1, 3, &Neighborhood::eight_way()),
+            vec![67]
+        );
+        assert_eq!(
+            get_symbol_part_numbers(&part_numbers, 1, 3, &Neighborhood::radius(2)),
+            vec![12, 67, 345]
+        );
+        assert_eq!(
+            get_symbol_part_numbers(
+                &part_numbers,
+                1,
+                3,
+                &Neighborhood::custom(vec![(-1, -2), (-1, 2), (1, -1), (1, 0)])
+            ),
+            vec![12, 67, 345]
+        );
+        assert_eq!(
+            get_symbol_part_numbers(
+                &part_numbers,
+                0,
+                0,
+                &Neighborhood::eight_way().with_wraparound(4, 8)
+            ),
+            vec![9, 12, 345]
+        );
+    }
+
+    #[test]
+    fn test_get_symbols_custom() {
+        let lines = vec!["12a..".to_string(), "~~7~@".to_string()];
+        assert_eq!(
+            get_symbols(&lines, &SymbolSet::new("a@", ".~", "0123456789").unwrap()),
+            Ok(hashmap! {
+                (0, 2) => 'a',
+                (1, 4) => '@',
+            })
+        );
+    }
+
+    #[test]
+    fn test_get_symbols_unknown_char() {
+        let lines = vec!["467..114..".to_string(), "...*..x...".to_string()];
+        assert_eq!(
+            get_symbols(&lines, &SymbolSet::default()),
+            Err(SchematicError::UnknownChar {
+                line: 1,
+                col: 6,
+                c: 'x'
+            })
+        );
+    }
+}
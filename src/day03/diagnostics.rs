@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use super::neighborhood::Neighborhood;
+use super::{is_part_number, PartSpan};
+
+/// Something in a schematic that does not contribute to either puzzle answer as expected.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Diagnostic {
+    /// A number with no adjacent symbol.
+    OrphanNumber(PartSpan),
+    /// A symbol with no adjacent number.
+    IsolatedSymbol {
+        line: usize,
+        col: usize,
+        symbol: char,
+    },
+    /// A `*` with one, or three or more, adjacent numbers, so it is not a gear.
+    NonGearStar {
+        line: usize,
+        col: usize,
+        neighbors: Vec<PartSpan>,
+    },
+}
+
+impl Diagnostic {
+    fn position(&self) -> (usize, usize) {
+        match self {
+            Diagnostic::OrphanNumber(span) => (span.line, span.start),
+            Diagnostic::IsolatedSymbol { line, col, .. } => (*line, *col),
+            Diagnostic::NonGearStar { line, col, .. } => (*line, *col),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Diagnostic::OrphanNumber(span) => write!(
+                f,
+                "orphan number {} at line {}, columns {}-{}",
+                span.value,
+                span.line,
+                span.start,
+                span.end - 1
+            ),
+            Diagnostic::IsolatedSymbol { line, col, symbol } => write!(
+                f,
+                "isolated symbol {:?} at line {}, column {}",
+                symbol, line, col
+            ),
+            Diagnostic::NonGearStar {
+                line,
+                col,
+                neighbors,
+            } => write!(
+                f,
+                "'*' at line {}, column {} has {} adjacent numbers: {}",
+                line,
+                col,
+                neighbors.len(),
+                neighbors
+                    .iter()
+                    .map(|span| format!("{} at ({}, {})", span.value, span.line, span.start))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+/// Every orphan number, isolated symbol and non-gear `*`, ordered by position.
+pub fn get_diagnostics(
+    spans: &[PartSpan],
+    symbols: &HashMap<(usize, usize), char>,
+    neighborhood: &Neighborhood,
+) -> Vec<Diagnostic> {
+    let mut out = spans
+        .iter()
+        .filter(|span| !is_part_number(span, symbols, neighborhood))
+        .map(|span| Diagnostic::OrphanNumber(*span))
+        .collect::<Vec<Diagnostic>>();
+    for ((line_i, row_i), c) in symbols {
+        let neighbors = spans
+            .iter()
+            .filter(|span| span.touches(*line_i, *row_i, neighborhood))
+            .copied()
+            .collect::<Vec<PartSpan>>();
+        if neighbors.is_empty() {
+            out.push(Diagnostic::IsolatedSymbol {
+                line: *line_i,
+                col: *row_i,
+                symbol: *c,
+            });
+        } else if *c == '*' && neighbors.len() != 2 {
+            out.push(Diagnostic::NonGearStar {
+                line: *line_i,
+                col: *row_i,
+                neighbors,
+            });
+        }
+    }
+    out.sort_by_key(|diagnostic| diagnostic.position());
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day03::symbols::SymbolSet;
+    use crate::day03::{get_part_spans, get_symbols};
+
+    #[test]
+    fn test_get_diagnostics() {
+        let lines = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+&.592.....
+......755.
+...$.*....
+.664.598*1
+.......1.."
+            .split('\n')
+            .map(|line| line.to_string())
+            .collect::<Vec<String>>();
+        let diagnostics = get_diagnostics(
+            &get_part_spans(&lines, &SymbolSet::default()).unwrap(),
+            &get_symbols(&lines, &SymbolSet::default()).unwrap(),
+            &Neighborhood::default(),
+        );
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|diagnostic| diagnostic.to_string())
+                .collect::<Vec<String>>(),
+            vec![
+                "orphan number 114 at line 0, columns 5-7",
+                "'*' at line 4, column 3 has 1 adjacent numbers: 617 at (4, 0)",
+                "orphan number 58 at line 5, columns 7-8",
+                "isolated symbol '&' at line 6, column 0",
+                "'*' at line 9, column 8 has 3 adjacent numbers: 598 at (9, 5), 1 at (9, 9), 1 at (10, 7)",
+            ]
+        );
+    }
+}
@@ -0,0 +1,330 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+use super::neighborhood::Neighborhood;
+use super::symbols::{CellKind, SymbolSet};
+use super::{get_gears, is_part_number, PartSpan, SchematicError};
+
+/// How number tokens are read from a schematic.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Dialect {
+    /// The puzzle's reading: a number is a run of digits, `-` is a symbol and `.` is blank.
+    #[default]
+    Classic,
+    /// A `-` directly before a digit is the number's sign, and a `.` between two digits is its
+    /// decimal point, e.g. `-12.5`. A `-` directly after a number is still a symbol.
+    Signed,
+}
+
+impl FromStr for Dialect {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Dialect, String> {
+        match s {
+            "classic" => Ok(Dialect::Classic),
+            "signed" => Ok(Dialect::Signed),
+            _ => Err(format!("unknown dialect: {}", s)),
+        }
+    }
+}
+
+/// An exact signed decimal, `units / 10^scale`, always stored without trailing fractional zeros
+/// so equal values compare equal.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct Decimal {
+    units: i128,
+    scale: u32,
+}
+
+impl Decimal {
+    fn new(mut units: i128, mut scale: u32) -> Decimal {
+        while scale > 0 && units % 10 == 0 {
+            units /= 10;
+            scale -= 1;
+        }
+        Decimal { units, scale }
+    }
+
+    /// `units` scaled up to `scale` decimal places, if it fits.
+    fn rescale(&self, scale: u32) -> Option<i128> {
+        10i128
+            .checked_pow(scale - self.scale)
+            .and_then(|factor| self.units.checked_mul(factor))
+    }
+
+    pub fn checked_add(self, other: Decimal) -> Result<Decimal, SchematicError> {
+        let scale = self.scale.max(other.scale);
+        self.rescale(scale)
+            .zip(other.rescale(scale))
+            .and_then(|(a, b)| a.checked_add(b))
+            .map(|units| Decimal::new(units, scale))
+            .ok_or_else(|| {
+                SchematicError::DecimalOverflow(format!("overflow adding {} and {}", self, other))
+            })
+    }
+
+    pub fn checked_mul(self, other: Decimal) -> Result<Decimal, SchematicError> {
+        self.units
+            .checked_mul(other.units)
+            .zip(self.scale.checked_add(other.scale))
+            .filter(|(_, scale)| *scale <= 38)
+            .map(|(units, scale)| Decimal::new(units, scale))
+            .ok_or_else(|| {
+                SchematicError::DecimalOverflow(format!(
+                    "overflow multiplying {} and {}",
+                    self, other
+                ))
+            })
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = SchematicError;
+
+    /// Parses `-?digits(.digits)?`.
+    fn from_str(s: &str) -> Result<Decimal, SchematicError> {
+        let (whole, frac) = s.split_once('.').unwrap_or((s, ""));
+        let is_digits = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+        if !is_digits(whole.strip_prefix('-').unwrap_or(whole))
+            || (s.contains('.') && !is_digits(frac))
+        {
+            return Err(SchematicError::InvalidDecimal(s.to_string()));
+        }
+        let units = format!("{}{}", whole, frac)
+            .parse::<i128>()
+            .map_err(|_| SchematicError::DecimalOverflow(format!("number too large: {}", s)))?;
+        Ok(Decimal::new(units, frac.len() as u32))
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = format!(
+            "{:0>width$}",
+            self.units.unsigned_abs(),
+            width = self.scale as usize + 1
+        );
+        let (whole, frac) = digits.split_at(digits.len() - self.scale as usize);
+        let sign = if self.units < 0 { "-" } else { "" };
+        if frac.is_empty() {
+            write!(f, "{}{}", sign, whole)
+        } else {
+            write!(f, "{}{}.{}", sign, whole, frac)
+        }
+    }
+}
+
+/// A signed-dialect number and the cells it covers, sign and decimal point included.
+pub type DecimalSpan = PartSpan<Decimal>;
+
+/// A schematic read in the signed dialect.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DecimalParts {
+    pub spans: Vec<DecimalSpan>,
+    /// Every symbol not taken up as a sign.
+    pub symbols: HashMap<(usize, usize), char>,
+}
+
+pub fn get_decimal_parts(
+    lines: &[String],
+    symbol_set: &SymbolSet,
+) -> Result<DecimalParts, SchematicError> {
+    let mut spans = vec![];
+    let mut symbols = HashMap::new();
+    for (line_i, line) in lines.iter().enumerate() {
+        let chars = line.chars().collect::<Vec<char>>();
+        let is_digit = |i: usize| {
+            chars
+                .get(i)
+                .is_some_and(|c| symbol_set.classify(*c) == Some(CellKind::Digit))
+        };
+        let mut covered = HashSet::new();
+        let mut i = 0;
+        while i < chars.len() {
+            // tokens always end on a digit, so a `-` right after one is never a sign
+            let signed = chars[i] == '-' && is_digit(i + 1) && (i == 0 || !is_digit(i - 1));
+            if !signed && !is_digit(i) {
+                i += 1;
+                continue;
+            }
+            let start = i;
+            i += if signed { 1 } else { 0 };
+            while is_digit(i) {
+                i += 1;
+            }
+            if chars.get(i) == Some(&'.') && is_digit(i + 1) {
+                i += 1;
+                while is_digit(i) {
+                    i += 1;
+                }
+            }
+            covered.extend(start..i);
+            spans.push(DecimalSpan {
+                line: line_i,
+                start,
+                end: i,
+                value: chars[start..i].iter().collect::<String>().parse()?,
+            });
+        }
+        for (row_i, c) in chars.iter().enumerate() {
+            if covered.contains(&row_i) {
+                continue;
+            }
+            match symbol_set.classify(*c) {
+                Some(CellKind::Symbol) => {
+                    symbols.insert((line_i, row_i), *c);
+                }
+                Some(_) => (),
+                None => {
+                    return Err(SchematicError::UnknownChar {
+                        line: line_i,
+                        col: row_i,
+                        c: *c,
+                    })
+                }
+            }
+        }
+    }
+    Ok(DecimalParts { spans, symbols })
+}
+
+/// The sum of numbers adjacent to any symbol and the sum of gear ratios, read in the signed
+/// dialect.
+pub fn solve_decimal(
+    lines: &[String],
+    symbol_set: &SymbolSet,
+    neighborhood: &Neighborhood,
+) -> Result<(Decimal, Decimal), SchematicError> {
+    let DecimalParts { spans, symbols } = get_decimal_parts(lines, symbol_set)?;
+    let mut part_sum = Decimal::default();
+    for span in spans
+        .iter()
+        .filter(|span| is_part_number(span, &symbols, neighborhood))
+    {
+        part_sum = part_sum.checked_add(span.value)?;
+    }
+    let mut gear_sum = Decimal::default();
+    for gear in get_gears(&spans, &symbols, neighborhood) {
+        gear_sum = gear_sum.checked_add(gear.parts[0].value.checked_mul(gear.parts[1].value)?)?;
+    }
+    Ok((part_sum, gear_sum))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn decimal(val: &str) -> Decimal {
+        val.parse().unwrap()
+    }
+
+    #[test]
+    fn test_decimal_round_trip() {
+        ["0", "7", "-7", "12.5", "-0.05", "1000"]
+            .iter()
+            .for_each(|val| assert_eq!(decimal(val).to_string(), *val));
+        assert_eq!(decimal("2.50"), decimal("2.5"));
+        assert_eq!(decimal("-0").to_string(), "0");
+    }
+
+    #[test]
+    fn test_decimal_from_str_invalid() {
+        ["", "-", "abc", ".5", "1.", "1.-5", "+1", "1.2.3"]
+            .iter()
+            .for_each(|val| {
+                assert_eq!(
+                    val.parse::<Decimal>(),
+                    Err(SchematicError::InvalidDecimal(val.to_string()))
+                )
+            });
+        assert_eq!(
+            "9".repeat(40).parse::<Decimal>(),
+            Err(SchematicError::DecimalOverflow(format!(
+                "number too large: {}",
+                "9".repeat(40)
+            )))
+        );
+    }
+
+    #[test]
+    fn test_decimal_arithmetic() {
+        assert_eq!(
+            decimal("1.25").checked_add(decimal("-3")),
+            Ok(decimal("-1.75"))
+        );
+        assert_eq!(decimal("0.5").checked_add(decimal("0.5")), Ok(decimal("1")));
+        assert_eq!(
+            decimal("-1.5").checked_mul(decimal("0.2")),
+            Ok(decimal("-0.3"))
+        );
+        assert_eq!(
+            decimal("1")
+                .checked_mul(decimal(&"9".repeat(38)))
+                .map(|val| val.to_string()),
+            Ok("9".repeat(38))
+        );
+        assert_eq!(
+            decimal(&"9".repeat(20)).checked_mul(decimal(&"9".repeat(20))),
+            Err(SchematicError::DecimalOverflow(format!(
+                "overflow multiplying {} and {}",
+                "9".repeat(20),
+                "9".repeat(20)
+            )))
+        );
+    }
+
+    #[test]
+    fn test_get_decimal_parts() {
+        let lines = ["-4.5*..7-2", ".-.3..$...", "1.2.3..-.."]
+            .iter()
+            .map(|line| line.to_string())
+            .collect::<Vec<String>>();
+        let DecimalParts { spans, symbols } =
+            get_decimal_parts(&lines, &SymbolSet::default()).unwrap();
+        assert_eq!(
+            spans
+                .iter()
+                .map(|span| (span.line, span.start, span.end, span.value.to_string()))
+                .collect::<Vec<(usize, usize, usize, String)>>(),
+            vec![
+                (0, 0, 4, "-4.5".to_string()),
+                (0, 7, 8, "7".to_string()),
+                (0, 9, 10, "2".to_string()),
+                (1, 3, 4, "3".to_string()),
+                (2, 0, 3, "1.2".to_string()),
+                (2, 4, 5, "3".to_string()),
+            ]
+        );
+        assert_eq!(
+            symbols,
+            hashmap! {
+                (0, 4) => '*',
+                (0, 8) => '-',
+                (1, 1) => '-',
+                (1, 6) => '$',
+                (2, 7) => '-',
+            }
+        );
+    }
+
+    #[test]
+    fn test_solve_decimal() {
+        let lines = ["-4.5*0.5.", "........1", ".2*-3..#."]
+            .iter()
+            .map(|line| line.to_string())
+            .collect::<Vec<String>>();
+        let (part_sum, gear_sum) =
+            solve_decimal(&lines, &SymbolSet::default(), &Neighborhood::default()).unwrap();
+        // 1 touches only the `#`; -4.5 and 0.5 share the first `*`, 2 and -3 the second
+        assert_eq!(part_sum, decimal("-4"));
+        assert_eq!(gear_sum, decimal("-8.25"));
+    }
+
+    #[test]
+    fn test_dialect_default() {
+        assert_eq!(Dialect::default(), Dialect::Classic);
+        assert_eq!("signed".parse(), Ok(Dialect::Signed));
+        assert!("fancy".parse::<Dialect>().is_err());
+    }
+}
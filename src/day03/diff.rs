@@ -0,0 +1,289 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use crate::arith::checked_sum;
+
+use super::neighborhood::Neighborhood;
+use super::symbols::SymbolSet;
+use super::{get_gear_ratios, get_part_nums, get_symbols, get_unique_part_numbers, SchematicError};
+
+/// A single element-level difference between two schematics. Numbers are identified by the
+/// position of their first digit.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Change {
+    NumberAdded {
+        line: usize,
+        col: usize,
+        value: u64,
+    },
+    NumberRemoved {
+        line: usize,
+        col: usize,
+        value: u64,
+    },
+    NumberChanged {
+        line: usize,
+        col: usize,
+        old: u64,
+        new: u64,
+    },
+    SymbolAdded {
+        line: usize,
+        col: usize,
+        symbol: char,
+    },
+    SymbolRemoved {
+        line: usize,
+        col: usize,
+        symbol: char,
+    },
+    SymbolChanged {
+        line: usize,
+        col: usize,
+        old: char,
+        new: char,
+    },
+    SymbolMoved {
+        from: (usize, usize),
+        to: (usize, usize),
+        symbol: char,
+    },
+}
+
+impl Change {
+    fn position(&self) -> (usize, usize) {
+        match self {
+            Change::NumberAdded { line, col, .. }
+            | Change::NumberRemoved { line, col, .. }
+            | Change::NumberChanged { line, col, .. }
+            | Change::SymbolAdded { line, col, .. }
+            | Change::SymbolRemoved { line, col, .. }
+            | Change::SymbolChanged { line, col, .. } => (*line, *col),
+            Change::SymbolMoved { from, .. } => *from,
+        }
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::NumberAdded { line, col, value } => {
+                write!(f, "+ number {} at ({}, {})", value, line, col)
+            }
+            Change::NumberRemoved { line, col, value } => {
+                write!(f, "- number {} at ({}, {})", value, line, col)
+            }
+            Change::NumberChanged {
+                line,
+                col,
+                old,
+                new,
+            } => write!(f, "~ number at ({}, {}): {} -> {}", line, col, old, new),
+            Change::SymbolAdded { line, col, symbol } => {
+                write!(f, "+ symbol {:?} at ({}, {})", symbol, line, col)
+            }
+            Change::SymbolRemoved { line, col, symbol } => {
+                write!(f, "- symbol {:?} at ({}, {})", symbol, line, col)
+            }
+            Change::SymbolChanged {
+                line,
+                col,
+                old,
+                new,
+            } => write!(f, "~ symbol at ({}, {}): {:?} -> {:?}", line, col, old, new),
+            Change::SymbolMoved { from, to, symbol } => write!(
+                f,
+                "> symbol {:?} moved ({}, {}) -> ({}, {})",
+                symbol, from.0, from.1, to.0, to.1
+            ),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SchematicDiff {
+    pub changes: Vec<Change>,
+    /// Part-number sum before and after.
+    pub part_sum: (u64, u64),
+    /// Gear-ratio sum before and after.
+    pub gear_sum: (u64, u64),
+}
+
+impl SchematicDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Collapse the per-cell map from `get_part_nums` back into one entry per number, keyed by the
+/// position of its first digit.
+fn get_numbers(part_nums: &HashMap<(usize, usize), u64>) -> BTreeMap<(usize, usize), u64> {
+    part_nums
+        .iter()
+        .filter(|((line_i, row_i), _)| {
+            *row_i == 0 || !part_nums.contains_key(&(*line_i, row_i - 1))
+        })
+        .map(|(pos, value)| (*pos, *value))
+        .collect()
+}
+
+fn get_sums(
+    part_nums: &HashMap<(usize, usize), u64>,
+    symbols: &HashMap<(usize, usize), char>,
+    neighborhood: &Neighborhood,
+) -> Result<(u64, u64), SchematicError> {
+    Ok((
+        checked_sum(get_unique_part_numbers(part_nums, symbols, neighborhood))?,
+        checked_sum(get_gear_ratios(part_nums, symbols, neighborhood)?)?,
+    ))
+}
+
+/// Compare two schematics element by element. A symbol that disappears from one position and
+/// reappears elsewhere is reported as moved, pairing each removal with the nearest unclaimed
+/// addition of the same character.
+pub fn diff(
+    old_lines: &[String],
+    new_lines: &[String],
+    symbol_set: &SymbolSet,
+    neighborhood: &Neighborhood,
+) -> Result<SchematicDiff, SchematicError> {
+    let old_part_nums = get_part_nums(old_lines, symbol_set)?;
+    let new_part_nums = get_part_nums(new_lines, symbol_set)?;
+    let old_symbols = get_symbols(old_lines, symbol_set)?;
+    let new_symbols = get_symbols(new_lines, symbol_set)?;
+    let mut changes = vec![];
+
+    let old_numbers = get_numbers(&old_part_nums);
+    let new_numbers = get_numbers(&new_part_nums);
+    for (&(line, col), &old) in &old_numbers {
+        match new_numbers.get(&(line, col)) {
+            Some(&new) if new != old => changes.push(Change::NumberChanged {
+                line,
+                col,
+                old,
+                new,
+            }),
+            Some(_) => (),
+            None => changes.push(Change::NumberRemoved {
+                line,
+                col,
+                value: old,
+            }),
+        }
+    }
+    for (&(line, col), &value) in &new_numbers {
+        if !old_numbers.contains_key(&(line, col)) {
+            changes.push(Change::NumberAdded { line, col, value });
+        }
+    }
+
+    let mut removed = vec![];
+    for (&(line, col), &old) in &old_symbols {
+        match new_symbols.get(&(line, col)) {
+            Some(&new) if new != old => changes.push(Change::SymbolChanged {
+                line,
+                col,
+                old,
+                new,
+            }),
+            Some(_) => (),
+            None => removed.push(((line, col), old)),
+        }
+    }
+    let mut added = new_symbols
+        .iter()
+        .filter(|(pos, _)| !old_symbols.contains_key(pos))
+        .map(|(pos, symbol)| (*pos, *symbol))
+        .collect::<Vec<((usize, usize), char)>>();
+    removed.sort();
+    added.sort();
+    for (from, symbol) in removed {
+        let nearest = added
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, c))| *c == symbol)
+            .min_by_key(|(_, (to, _))| from.0.abs_diff(to.0).max(from.1.abs_diff(to.1)))
+            .map(|(i, _)| i);
+        match nearest {
+            Some(i) => {
+                let (to, _) = added.remove(i);
+                changes.push(Change::SymbolMoved { from, to, symbol });
+            }
+            None => changes.push(Change::SymbolRemoved {
+                line: from.0,
+                col: from.1,
+                symbol,
+            }),
+        }
+    }
+    for ((line, col), symbol) in added {
+        changes.push(Change::SymbolAdded { line, col, symbol });
+    }
+    changes.sort_by_key(|change| change.position());
+
+    let (old_part_sum, old_gear_sum) = get_sums(&old_part_nums, &old_symbols, neighborhood)?;
+    let (new_part_sum, new_gear_sum) = get_sums(&new_part_nums, &new_symbols, neighborhood)?;
+    Ok(SchematicDiff {
+        changes,
+        part_sum: (old_part_sum, new_part_sum),
+        gear_sum: (old_gear_sum, new_gear_sum),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day03::EXAMPLE;
+
+    fn to_lines(text: &str) -> Vec<String> {
+        text.lines().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn test_diff_identical() {
+        let lines = to_lines(EXAMPLE);
+        let diff = diff(
+            &lines,
+            &lines,
+            &SymbolSet::default(),
+            &Neighborhood::default(),
+        )
+        .unwrap();
+        assert!(diff.is_empty());
+        assert_eq!(diff.part_sum, (4361, 4361));
+        assert_eq!(diff.gear_sum, (467835, 467835));
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = to_lines(EXAMPLE);
+        let new = to_lines(
+            "467..114..
+...*......
+..36..633.
+......#...
+617*......
+.......58+
+..592.....
+......755.
+...#.*....
+.664.598.7",
+        );
+        let diff = diff(&old, &new, &SymbolSet::default(), &Neighborhood::default()).unwrap();
+        assert_eq!(
+            diff.changes
+                .iter()
+                .map(|change| change.to_string())
+                .collect::<Vec<String>>(),
+            vec![
+                "~ number at (2, 2): 35 -> 36",
+                "> symbol '+' moved (5, 5) -> (5, 9)",
+                "~ symbol at (8, 3): '$' -> '#'",
+                "+ number 7 at (9, 9)",
+            ]
+        );
+        // 592 loses its symbol while 58 gains one
+        assert_eq!(diff.part_sum, (4361, 4361 + 1 - 592 + 58));
+        assert_eq!(diff.gear_sum, (467835, 467 * 36 + 755 * 598));
+    }
+}
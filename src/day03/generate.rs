@@ -0,0 +1,320 @@
+use std::fmt;
+
+use super::symbols::{CellKind, SymbolSet};
+use crate::arith::{checked_add, checked_mul, OverflowError};
+use crate::rng::Rng;
+
+/// Shape of a generated schematic.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratorConfig {
+    pub seed: u64,
+    pub height: usize,
+    pub width: usize,
+    /// Chance that a free cell starts a number.
+    pub density: f64,
+    pub min_len: usize,
+    pub max_len: usize,
+    /// Chance that a free cell that does not start a number holds a symbol.
+    pub symbol_chance: f64,
+    /// Chance that a symbol is a `*`.
+    pub gear_chance: f64,
+    /// Symbols other than `*` to choose from; each must be a symbol of the default
+    /// [`SymbolSet`].
+    pub symbols: Vec<char>,
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> GeneratorConfig {
+        GeneratorConfig {
+            seed: 0,
+            height: 140,
+            width: 140,
+            density: 0.1,
+            min_len: 1,
+            max_len: 3,
+            symbol_chance: 0.05,
+            gear_chance: 0.3,
+            symbols: "#$%&+-/=@".chars().collect(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum GeneratorError {
+    InvalidConfig(String),
+    Overflow(OverflowError),
+}
+
+impl From<OverflowError> for GeneratorError {
+    fn from(err: OverflowError) -> GeneratorError {
+        GeneratorError::Overflow(err)
+    }
+}
+
+impl fmt::Display for GeneratorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeneratorError::InvalidConfig(err) => write!(f, "invalid generator config: {}", err),
+            GeneratorError::Overflow(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for GeneratorError {}
+
+/// A generated schematic together with its expected part-number sum and gear-ratio sum.
+#[derive(Debug, PartialEq)]
+pub struct GeneratedSchematic {
+    pub lines: Vec<String>,
+    pub part_sum: u64,
+    pub gear_sum: u64,
+}
+
+struct Number {
+    line: usize,
+    start: usize,
+    end: usize,
+    value: u64,
+}
+
+impl Number {
+    /// Whether (`line`, `col`) is in the box one cell around the number.
+    fn borders(&self, line: usize, col: usize) -> bool {
+        line + 1 >= self.line && line <= self.line + 1 && col + 1 >= self.start && col <= self.end
+    }
+}
+
+/// Generate a schematic and compute its answers from the numbers and symbols as they are placed,
+/// without going through the scanner or solver.
+pub fn generate(config: &GeneratorConfig) -> Result<GeneratedSchematic, GeneratorError> {
+    if config.min_len < 1 || config.min_len > config.max_len {
+        return Err(GeneratorError::InvalidConfig(format!(
+            "invalid number length range {}..={}",
+            config.min_len, config.max_len
+        )));
+    }
+    // 20 digits no longer fit in a u64
+    if config.max_len > 19 {
+        return Err(GeneratorError::InvalidConfig(
+            "numbers may have at most 19 digits".to_string(),
+        ));
+    }
+    if config.gear_chance < 1.0 && config.symbols.is_empty() {
+        return Err(GeneratorError::InvalidConfig(
+            "at least one non-gear symbol is required".to_string(),
+        ));
+    }
+    // the answers below are computed against the puzzle's reading, so a symbol the solver would
+    // take for a digit or a blank would make them wrong
+    let symbol_set = SymbolSet::default();
+    if let Some(c) = config
+        .symbols
+        .iter()
+        .find(|c| symbol_set.classify(**c) != Some(CellKind::Symbol))
+    {
+        return Err(GeneratorError::InvalidConfig(format!(
+            "{:?} is not a symbol",
+            c
+        )));
+    }
+
+    let mut rng = Rng::new(config.seed);
+    let mut cells = vec![vec!['.'; config.width]; config.height];
+    let mut numbers = vec![];
+    for (line_i, line) in cells.iter_mut().enumerate() {
+        let mut col = 0;
+        while col < config.width {
+            if rng.chance(config.density) {
+                let len = (rng.range(config.min_len as u64, config.max_len as u64) as usize)
+                    .min(config.width - col);
+                let mut value = 0;
+                for (i, cell) in line[col..col + len].iter_mut().enumerate() {
+                    // no leading zeros, so the text and the value agree
+                    let digit = rng.range(if i == 0 { 1 } else { 0 }, 9);
+                    value = value * 10 + digit;
+                    *cell = (b'0' + digit as u8) as char;
+                }
+                numbers.push(Number {
+                    line: line_i,
+                    start: col,
+                    end: col + len,
+                    value,
+                });
+                // skip a cell so the next number cannot run into this one
+                col += len + 1;
+                continue;
+            }
+            if rng.chance(config.symbol_chance) {
+                line[col] = if rng.chance(config.gear_chance) {
+                    '*'
+                } else {
+                    config.symbols[rng.range(0, config.symbols.len() as u64 - 1) as usize]
+                };
+            }
+            col += 1;
+        }
+    }
+
+    let mut part_sum = 0;
+    let mut gear_sum = 0;
+    let symbols = cells
+        .iter()
+        .enumerate()
+        .flat_map(|(line_i, line)| {
+            line.iter()
+                .enumerate()
+                .filter(|(_, c)| **c != '.' && !c.is_ascii_digit())
+                .map(move |(col, c)| (line_i, col, *c))
+        })
+        .collect::<Vec<(usize, usize, char)>>();
+    for number in &numbers {
+        if symbols
+            .iter()
+            .any(|(line, col, _)| number.borders(*line, *col))
+        {
+            part_sum = checked_add(part_sum, number.value)?;
+        }
+    }
+    for (line, col, _) in symbols.iter().filter(|(_, _, c)| *c == '*') {
+        let adjacent = numbers
+            .iter()
+            .filter(|number| number.borders(*line, *col))
+            .collect::<Vec<&Number>>();
+        if let [a, b] = adjacent[..] {
+            gear_sum = checked_add(gear_sum, checked_mul(a.value, b.value)?)?;
+        }
+    }
+
+    Ok(GeneratedSchematic {
+        lines: cells
+            .into_iter()
+            .map(|line| line.into_iter().collect())
+            .collect(),
+        part_sum,
+        gear_sum,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day03::neighborhood::Neighborhood;
+    use crate::day03::solve;
+
+    #[test]
+    fn test_generate_number_layout() {
+        // every free cell starts a number, so each row is three-digit numbers one cell apart,
+        // with the last one cut short by the right edge
+        let generated = generate(&GeneratorConfig {
+            seed: 5,
+            height: 4,
+            width: 10,
+            density: 1.0,
+            min_len: 3,
+            max_len: 3,
+            ..GeneratorConfig::default()
+        })
+        .unwrap();
+        assert_eq!(generated.lines.len(), 4);
+        for line in &generated.lines {
+            let runs = line.split('.').collect::<Vec<&str>>();
+            assert_eq!(
+                runs.iter().map(|run| run.len()).collect::<Vec<usize>>(),
+                vec![3, 3, 2]
+            );
+            assert!(runs
+                .iter()
+                .all(|run| run.chars().all(|c| c.is_ascii_digit()) && !run.starts_with('0')));
+        }
+        assert_eq!(generated.part_sum, 0);
+        assert_eq!(generated.gear_sum, 0);
+    }
+
+    #[test]
+    fn test_generate_symbol_choice() {
+        let symbols = |gear_chance: f64, symbols: Vec<char>| {
+            generate(&GeneratorConfig {
+                seed: 9,
+                height: 3,
+                width: 20,
+                density: 0.0,
+                symbol_chance: 1.0,
+                gear_chance,
+                symbols,
+                ..GeneratorConfig::default()
+            })
+            .unwrap()
+            .lines
+            .concat()
+        };
+        assert_eq!(symbols(1.0, vec![]), "*".repeat(60));
+        assert_eq!(symbols(0.0, vec!['#']), "#".repeat(60));
+        assert!(symbols(0.0, vec!['@', '+'])
+            .chars()
+            .all(|c| c == '@' || c == '+'));
+    }
+
+    #[test]
+    fn test_generate_invalid_config() {
+        let invalid = |config: GeneratorConfig| generate(&config).unwrap_err();
+        assert_eq!(
+            invalid(GeneratorConfig {
+                symbols: vec!['1', '.'],
+                ..GeneratorConfig::default()
+            }),
+            GeneratorError::InvalidConfig("'1' is not a symbol".to_string())
+        );
+        assert_eq!(
+            invalid(GeneratorConfig {
+                symbols: vec!['#', 'a'],
+                ..GeneratorConfig::default()
+            }),
+            GeneratorError::InvalidConfig("'a' is not a symbol".to_string())
+        );
+        assert_eq!(
+            invalid(GeneratorConfig {
+                min_len: 4,
+                max_len: 3,
+                ..GeneratorConfig::default()
+            }),
+            GeneratorError::InvalidConfig("invalid number length range 4..=3".to_string())
+        );
+        assert_eq!(
+            invalid(GeneratorConfig {
+                max_len: 20,
+                ..GeneratorConfig::default()
+            }),
+            GeneratorError::InvalidConfig("numbers may have at most 19 digits".to_string())
+        );
+        assert_eq!(
+            invalid(GeneratorConfig {
+                symbols: vec![],
+                ..GeneratorConfig::default()
+            }),
+            GeneratorError::InvalidConfig("at least one non-gear symbol is required".to_string())
+        );
+    }
+
+    #[test]
+    fn test_generate_answers() {
+        (0..20).for_each(|seed| {
+            let generated = generate(&GeneratorConfig {
+                seed,
+                height: 40,
+                width: 60,
+                density: 0.2,
+                symbol_chance: 0.15,
+                ..GeneratorConfig::default()
+            })
+            .unwrap();
+            assert_eq!(
+                solve(
+                    &generated.lines,
+                    &SymbolSet::default(),
+                    &Neighborhood::default()
+                ),
+                Ok((generated.part_sum, generated.gear_sum))
+            );
+        })
+    }
+}
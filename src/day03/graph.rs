@@ -0,0 +1,304 @@
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+use super::neighborhood::Neighborhood;
+use super::PartSpan;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SymbolNode {
+    pub line: usize,
+    pub col: usize,
+    pub symbol: char,
+}
+
+/// A node of the graph, by index into [`SchematicGraph::symbols`] or [`SchematicGraph::numbers`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
+pub enum Node {
+    Symbol(usize),
+    Number(usize),
+}
+
+/// Bipartite graph linking each symbol to the part-number spans it touches.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SchematicGraph {
+    pub symbols: Vec<SymbolNode>,
+    pub numbers: Vec<PartSpan>,
+    symbol_edges: Vec<Vec<usize>>,
+    number_edges: Vec<Vec<usize>>,
+}
+
+impl SchematicGraph {
+    pub fn new(
+        spans: &[PartSpan],
+        symbols: &HashMap<(usize, usize), char>,
+        neighborhood: &Neighborhood,
+    ) -> SchematicGraph {
+        let mut numbers = spans.to_vec();
+        numbers.sort_by_key(|span| (span.line, span.start));
+        let cells = numbers
+            .iter()
+            .enumerate()
+            .flat_map(|(i, span)| (span.start..span.end).map(move |col| ((span.line, col), i)))
+            .collect::<HashMap<(usize, usize), usize>>();
+
+        let mut symbols = symbols
+            .iter()
+            .map(|((line, col), symbol)| SymbolNode {
+                line: *line,
+                col: *col,
+                symbol: *symbol,
+            })
+            .collect::<Vec<SymbolNode>>();
+        symbols.sort_by_key(|node| (node.line, node.col));
+
+        let mut number_edges = vec![Vec::new(); numbers.len()];
+        let symbol_edges = symbols
+            .iter()
+            .enumerate()
+            .map(|(symbol_i, node)| {
+                let mut edges = neighborhood
+                    .neighbors(node.line, node.col)
+                    .iter()
+                    .filter_map(|cell| cells.get(cell).copied())
+                    .collect::<Vec<usize>>();
+                edges.sort();
+                edges.dedup();
+                edges
+                    .iter()
+                    .for_each(|number_i| number_edges[*number_i].push(symbol_i));
+                edges
+            })
+            .collect::<Vec<Vec<usize>>>();
+
+        SchematicGraph {
+            symbols,
+            numbers,
+            symbol_edges,
+            number_edges,
+        }
+    }
+
+    pub fn symbol_at(&self, line: usize, col: usize) -> Option<usize> {
+        self.symbols
+            .iter()
+            .position(|node| node.line == line && node.col == col)
+    }
+
+    pub fn number_at(&self, line: usize, col: usize) -> Option<usize> {
+        self.numbers
+            .iter()
+            .position(|span| span.line == line && span.start <= col && col < span.end)
+    }
+
+    /// The numbers touched by symbol `symbol_i`.
+    pub fn symbol_neighbors(&self, symbol_i: usize) -> Vec<&PartSpan> {
+        self.symbol_edges[symbol_i]
+            .iter()
+            .map(|number_i| &self.numbers[*number_i])
+            .collect()
+    }
+
+    /// The symbols touching number `number_i`.
+    pub fn number_neighbors(&self, number_i: usize) -> Vec<&SymbolNode> {
+        self.number_edges[number_i]
+            .iter()
+            .map(|symbol_i| &self.symbols[*symbol_i])
+            .collect()
+    }
+
+    fn edges(&self, node: Node) -> Vec<Node> {
+        match node {
+            Node::Symbol(i) => self.symbol_edges[i]
+                .iter()
+                .map(|j| Node::Number(*j))
+                .collect(),
+            Node::Number(i) => self.number_edges[i]
+                .iter()
+                .map(|j| Node::Symbol(*j))
+                .collect(),
+        }
+    }
+
+    /// Every connected component, including lone nodes, each sorted with symbols first.
+    pub fn connected_components(&self) -> Vec<Vec<Node>> {
+        let mut seen: HashSet<Node> = HashSet::new();
+        let mut out = Vec::new();
+        let nodes = (0..self.symbols.len())
+            .map(Node::Symbol)
+            .chain((0..self.numbers.len()).map(Node::Number));
+        for start in nodes {
+            if !seen.insert(start) {
+                continue;
+            }
+            let mut component = vec![start];
+            let mut queue = VecDeque::from([start]);
+            while let Some(node) = queue.pop_front() {
+                for next in self.edges(node) {
+                    if seen.insert(next) {
+                        component.push(next);
+                        queue.push_back(next);
+                    }
+                }
+            }
+            component.sort();
+            out.push(component);
+        }
+        out
+    }
+
+    /// How many symbols and how many numbers have each degree.
+    pub fn degree_distribution(&self) -> (BTreeMap<usize, usize>, BTreeMap<usize, usize>) {
+        let histogram = |edges: &[Vec<usize>]| {
+            let mut out = BTreeMap::new();
+            edges
+                .iter()
+                .for_each(|edges| *out.entry(edges.len()).or_insert(0) += 1);
+            out
+        };
+        (histogram(&self.symbol_edges), histogram(&self.number_edges))
+    }
+
+    /// Graphviz DOT, with each component of more than one node drawn as a cluster.
+    pub fn to_dot(&self) -> String {
+        let id = |node: &Node| match node {
+            Node::Symbol(i) => format!("s{}", i),
+            Node::Number(i) => format!("n{}", i),
+        };
+        let mut out = "graph schematic {\n".to_string();
+        for (component_i, component) in self.connected_components().iter().enumerate() {
+            let indent = if component.len() > 1 {
+                out.push_str(&format!("  subgraph cluster_{} {{\n", component_i));
+                "    "
+            } else {
+                "  "
+            };
+            for node in component {
+                let (label, shape) = match node {
+                    Node::Symbol(i) => {
+                        let symbol = &self.symbols[*i];
+                        (
+                            format!("{} ({}, {})", symbol.symbol, symbol.line, symbol.col),
+                            "box",
+                        )
+                    }
+                    Node::Number(i) => {
+                        let span = &self.numbers[*i];
+                        (
+                            format!("{} ({}, {})", span.value, span.line, span.start),
+                            "ellipse",
+                        )
+                    }
+                };
+                out.push_str(&format!(
+                    "{}{} [label=\"{}\" shape={}];\n",
+                    indent,
+                    id(node),
+                    label.replace('\\', "\\\\").replace('"', "\\\""),
+                    shape
+                ));
+            }
+            if component.len() > 1 {
+                out.push_str("  }\n");
+            }
+        }
+        for (symbol_i, edges) in self.symbol_edges.iter().enumerate() {
+            for number_i in edges {
+                out.push_str(&format!("  s{} -- n{};\n", symbol_i, number_i));
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day03::symbols::SymbolSet;
+    use crate::day03::EXAMPLE;
+    use crate::day03::{get_part_spans, get_symbols};
+
+    fn graph(input: &str) -> SchematicGraph {
+        let lines = input
+            .lines()
+            .map(|line| line.to_string())
+            .collect::<Vec<String>>();
+        SchematicGraph::new(
+            &get_part_spans(&lines, &SymbolSet::default()).unwrap(),
+            &get_symbols(&lines, &SymbolSet::default()).unwrap(),
+            &Neighborhood::default(),
+        )
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let graph = graph(EXAMPLE);
+        assert_eq!(
+            graph
+                .symbol_neighbors(graph.symbol_at(1, 3).unwrap())
+                .iter()
+                .map(|span| span.value)
+                .collect::<Vec<u64>>(),
+            vec![467, 35]
+        );
+        assert_eq!(
+            graph
+                .number_neighbors(graph.number_at(9, 2).unwrap())
+                .iter()
+                .map(|symbol| symbol.symbol)
+                .collect::<Vec<char>>(),
+            vec!['$']
+        );
+        assert!(graph
+            .number_neighbors(graph.number_at(0, 6).unwrap())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let graph = graph(
+            "1.2.3
+*.#..
+4....
+.....
+5$6.7",
+        );
+        assert_eq!(
+            graph.connected_components(),
+            vec![
+                vec![Node::Symbol(0), Node::Number(0), Node::Number(3)],
+                vec![Node::Symbol(1), Node::Number(1)],
+                vec![Node::Symbol(2), Node::Number(4), Node::Number(5)],
+                vec![Node::Number(2)],
+                vec![Node::Number(6)],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_degree_distribution() {
+        let graph = graph("1.2.3\n*.#..\n4....\n.....\n5$6.7");
+        assert_eq!(
+            graph.degree_distribution(),
+            (btreemap! {1 => 1, 2 => 2}, btreemap! {0 => 2, 1 => 5})
+        );
+    }
+
+    #[test]
+    fn test_to_dot() {
+        assert_eq!(
+            graph("1\"\n*.\n.3").to_dot(),
+            r#"graph schematic {
+  subgraph cluster_0 {
+    s0 [label="\" (0, 1)" shape=box];
+    s1 [label="* (1, 0)" shape=box];
+    n0 [label="1 (0, 0)" shape=ellipse];
+    n1 [label="3 (2, 1)" shape=ellipse];
+  }
+  s0 -- n0;
+  s1 -- n0;
+  s1 -- n1;
+}
+"#
+        );
+    }
+}
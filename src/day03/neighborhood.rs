@@ -0,0 +1,161 @@
+use std::str::FromStr;
+
+/// The cells counted as adjacent to a cell, as (line, column) offsets, and whether the schematic
+/// wraps around at its edges.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Neighborhood {
+    offsets: Vec<(isize, isize)>,
+    /// Height and width of the schematic when it wraps around like a torus.
+    wrap: Option<(usize, usize)>,
+}
+
+impl Neighborhood {
+    /// The cells directly above, below, left and right.
+    pub fn four_way() -> Neighborhood {
+        Neighborhood::custom(vec![(-1, 0), (0, -1), (0, 1), (1, 0)])
+    }
+
+    /// The 8 surrounding cells, including diagonals.
+    pub fn eight_way() -> Neighborhood {
+        Neighborhood::radius(1)
+    }
+
+    /// Every cell within Chebyshev distance `r`.
+    pub fn radius(r: usize) -> Neighborhood {
+        let r = r as isize;
+        Neighborhood::custom(
+            (-r..=r)
+                .flat_map(|dl| (-r..=r).map(move |dc| (dl, dc)))
+                .collect(),
+        )
+    }
+
+    /// An arbitrary list of offsets; `(0, 0)` is ignored.
+    pub fn custom(offsets: Vec<(isize, isize)>) -> Neighborhood {
+        Neighborhood {
+            offsets: offsets
+                .into_iter()
+                .filter(|offset| *offset != (0, 0))
+                .collect(),
+            wrap: None,
+        }
+    }
+
+    /// Wrap offsets that leave a `height` by `width` schematic around to the opposite edge.
+    pub fn with_wraparound(self, height: usize, width: usize) -> Neighborhood {
+        assert!(height > 0 && width > 0, "cannot wrap an empty schematic");
+        Neighborhood {
+            wrap: Some((height, width)),
+            ..self
+        }
+    }
+
+    pub fn offsets(&self) -> &[(isize, isize)] {
+        &self.offsets
+    }
+
+    /// The cells neighboring (`line`, `col`). Without wraparound, cells before the first line or
+    /// column are dropped.
+    pub fn neighbors(&self, line: usize, col: usize) -> Vec<(usize, usize)> {
+        self.offsets
+            .iter()
+            .filter_map(|(dl, dc)| {
+                let line = line as isize + dl;
+                let col = col as isize + dc;
+                match self.wrap {
+                    Some((height, width)) => Some((
+                        line.rem_euclid(height as isize) as usize,
+                        col.rem_euclid(width as isize) as usize,
+                    )),
+                    None if line >= 0 && col >= 0 => Some((line as usize, col as usize)),
+                    None => None,
+                }
+            })
+            .collect()
+    }
+}
+
+impl Default for Neighborhood {
+    fn default() -> Neighborhood {
+        Neighborhood::eight_way()
+    }
+}
+
+/// Parses `4`, `8`, `r<radius>` or a list of offsets such as `-1:0,1:0`.
+impl FromStr for Neighborhood {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Neighborhood, String> {
+        match s {
+            "4" => Ok(Neighborhood::four_way()),
+            "8" => Ok(Neighborhood::eight_way()),
+            _ => match s.strip_prefix('r') {
+                Some(r) => r
+                    .parse::<usize>()
+                    .map(Neighborhood::radius)
+                    .map_err(|_| format!("unable to parse radius: {}", r)),
+                None => s
+                    .split(',')
+                    .map(|offset| {
+                        offset
+                            .split_once(':')
+                            .and_then(|(dl, dc)| Some((dl.parse().ok()?, dc.parse().ok()?)))
+                            .ok_or_else(|| format!("unable to parse offset: {}", offset))
+                    })
+                    .collect::<Result<Vec<(isize, isize)>, String>>()
+                    .map(Neighborhood::custom),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_neighbors() {
+        let mut neighbors = Neighborhood::eight_way().neighbors(1, 1);
+        neighbors.sort();
+        assert_eq!(
+            neighbors,
+            vec![
+                (0, 0),
+                (0, 1),
+                (0, 2),
+                (1, 0),
+                (1, 2),
+                (2, 0),
+                (2, 1),
+                (2, 2)
+            ]
+        );
+        assert_eq!(
+            Neighborhood::four_way().neighbors(0, 0),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(Neighborhood::radius(2).offsets().len(), 24);
+    }
+
+    #[test]
+    fn test_neighbors_wraparound() {
+        let mut neighbors = Neighborhood::four_way()
+            .with_wraparound(3, 5)
+            .neighbors(0, 4);
+        neighbors.sort();
+        assert_eq!(neighbors, vec![(0, 0), (0, 3), (1, 4), (2, 4)]);
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("4".parse(), Ok(Neighborhood::four_way()));
+        assert_eq!("8".parse(), Ok(Neighborhood::eight_way()));
+        assert_eq!("r3".parse(), Ok(Neighborhood::radius(3)));
+        assert_eq!(
+            "-1:0,0:2".parse(),
+            Ok(Neighborhood::custom(vec![(-1, 0), (0, 2)]))
+        );
+        assert!("r".parse::<Neighborhood>().is_err());
+        assert!("1:x".parse::<Neighborhood>().is_err());
+    }
+}
@@ -0,0 +1,141 @@
+use super::neighborhood::Neighborhood;
+use super::symbols::{CellKind, SymbolSet};
+use super::{get_gears, get_part_spans, get_symbols, is_part_number, SchematicError};
+
+const RESET: &str = "\x1b[0m";
+
+/// How a cell is drawn when rendering with color.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Style {
+    Plain,
+    PartNumber,
+    LoneNumber,
+    Gear,
+    NonGear,
+}
+
+impl Style {
+    fn code(&self) -> &'static str {
+        match self {
+            Style::Plain => RESET,
+            Style::PartNumber => "\x1b[32m",
+            Style::LoneNumber => "\x1b[31m",
+            Style::Gear => "\x1b[1;33m",
+            Style::NonGear => "\x1b[2m",
+        }
+    }
+}
+
+/// Area to crop the output to: every cell within `radius` of (`line`, `col`).
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Window {
+    pub line: usize,
+    pub col: usize,
+    pub radius: usize,
+}
+
+impl Window {
+    fn contains(&self, line: usize, col: usize) -> bool {
+        line.abs_diff(self.line) <= self.radius && col.abs_diff(self.col) <= self.radius
+    }
+}
+
+/// Render a schematic, optionally cropped to `window`. With `color`, part numbers touching a
+/// symbol are green, lone numbers red, gears bold yellow and `*` symbols that are not gears dim.
+pub fn render(
+    lines: &[String],
+    symbol_set: &SymbolSet,
+    color: bool,
+    window: Option<&Window>,
+) -> Result<String, SchematicError> {
+    let spans = get_part_spans(lines, symbol_set)?;
+    let symbols = get_symbols(lines, symbol_set)?;
+    let neighborhood = Neighborhood::default();
+    let gears = get_gears(&spans, &symbols, &neighborhood);
+
+    let mut out = String::new();
+    for (line_i, line) in lines.iter().enumerate() {
+        if window.is_some_and(|window| line_i.abs_diff(window.line) > window.radius) {
+            continue;
+        }
+        let mut current = Style::Plain;
+        for (row_i, c) in line.chars().enumerate() {
+            if window.is_some_and(|window| !window.contains(line_i, row_i)) {
+                continue;
+            }
+            let style = match symbol_set.classify(c) {
+                Some(CellKind::Digit) => {
+                    let span = spans
+                        .iter()
+                        .find(|span| span.line == line_i && span.start <= row_i && row_i < span.end)
+                        .expect("digit outside of a part number");
+                    if is_part_number(span, &symbols, &neighborhood) {
+                        Style::PartNumber
+                    } else {
+                        Style::LoneNumber
+                    }
+                }
+                Some(CellKind::Symbol) if c == '*' => {
+                    if gears
+                        .iter()
+                        .any(|gear| gear.line == line_i && gear.col == row_i)
+                    {
+                        Style::Gear
+                    } else {
+                        Style::NonGear
+                    }
+                }
+                _ => Style::Plain,
+            };
+            if color && style != current {
+                out.push_str(style.code());
+                current = style;
+            }
+            out.push(c);
+        }
+        if color && current != Style::Plain {
+            out.push_str(RESET);
+        }
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day03::example_lines;
+
+    #[test]
+    fn test_render_plain() {
+        assert_eq!(
+            render(&example_lines(), &SymbolSet::default(), false, None),
+            Ok(include_str!("snapshots/render_plain.txt").to_string())
+        )
+    }
+
+    #[test]
+    fn test_render_color() {
+        assert_eq!(
+            render(&example_lines(), &SymbolSet::default(), true, None),
+            Ok(include_str!("snapshots/render_color.txt").to_string())
+        )
+    }
+
+    #[test]
+    fn test_render_window() {
+        assert_eq!(
+            render(
+                &example_lines(),
+                &SymbolSet::default(),
+                true,
+                Some(&Window {
+                    line: 4,
+                    col: 3,
+                    radius: 2
+                })
+            ),
+            Ok(include_str!("snapshots/render_window.txt").to_string())
+        )
+    }
+}
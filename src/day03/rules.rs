@@ -0,0 +1,279 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use super::neighborhood::Neighborhood;
+use super::PartSpan;
+use crate::arith::{checked_add, checked_product, checked_sum, OverflowError};
+
+/// How the part numbers around a matching symbol are combined into one value.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Aggregation {
+    Product,
+    Sum,
+    Min,
+    Max,
+    Count,
+}
+
+impl Aggregation {
+    /// Combine `values`, or `None` for `Min` and `Max` when there are no values. Like `Sum`,
+    /// `Product` is 0 when there are no values, so a symbol without numbers adds nothing.
+    pub fn apply(&self, values: &[u64]) -> Result<Option<u64>, OverflowError> {
+        Ok(match self {
+            Aggregation::Product if values.is_empty() => Some(0),
+            Aggregation::Product => Some(checked_product(values.iter().copied())?),
+            Aggregation::Sum => Some(checked_sum(values.iter().copied())?),
+            Aggregation::Min => values.iter().min().copied(),
+            Aggregation::Max => values.iter().max().copied(),
+            Aggregation::Count => Some(values.len() as u64),
+        })
+    }
+}
+
+impl FromStr for Aggregation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Aggregation, String> {
+        match s {
+            "product" => Ok(Aggregation::Product),
+            "sum" => Ok(Aggregation::Sum),
+            "min" => Ok(Aggregation::Min),
+            "max" => Ok(Aggregation::Max),
+            "count" => Ok(Aggregation::Count),
+            _ => Err(format!("unknown aggregation: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for Aggregation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Aggregation::Product => "product",
+            Aggregation::Sum => "sum",
+            Aggregation::Min => "min",
+            Aggregation::Max => "max",
+            Aggregation::Count => "count",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Symbols written as `symbol` with between `min_adjacent` and `max_adjacent` (inclusive, unbounded
+/// if `None`) adjacent part numbers contribute the `aggregation` of those numbers.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Rule {
+    pub symbol: char,
+    pub min_adjacent: usize,
+    pub max_adjacent: Option<usize>,
+    pub aggregation: Aggregation,
+}
+
+impl Rule {
+    /// The puzzle's rule: a `*` with exactly two adjacent numbers contributes their product.
+    pub fn gear_ratio() -> Rule {
+        Rule {
+            symbol: '*',
+            min_adjacent: 2,
+            max_adjacent: Some(2),
+            aggregation: Aggregation::Product,
+        }
+    }
+
+    pub fn matches(&self, symbol: char, adjacent: usize) -> bool {
+        symbol == self.symbol
+            && adjacent >= self.min_adjacent
+            && self.max_adjacent.is_none_or(|max| adjacent <= max)
+    }
+}
+
+/// Parses `symbol:count:aggregation`, where count is `n`, `n-m` or `n-`, e.g. `*:2:product`.
+impl FromStr for Rule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Rule, String> {
+        let mut chars = s.chars();
+        let symbol = chars.next().ok_or_else(|| "empty rule".to_string())?;
+        let bits = chars
+            .as_str()
+            .strip_prefix(':')
+            .ok_or_else(|| format!("expected symbol:count:aggregation but got: {}", s))?
+            .split(':')
+            .collect::<Vec<&str>>();
+        let [count, aggregation] = bits[..] else {
+            return Err(format!("expected symbol:count:aggregation but got: {}", s));
+        };
+        let parse_count = |val: &str| {
+            val.parse::<usize>()
+                .map_err(|_| format!("unable to parse count: {}", val))
+        };
+        let (min_adjacent, max_adjacent) = match count.split_once('-') {
+            Some((min, "")) => (parse_count(min)?, None),
+            Some((min, max)) => (parse_count(min)?, Some(parse_count(max)?)),
+            None => (parse_count(count)?, Some(parse_count(count)?)),
+        };
+        if max_adjacent.is_some_and(|max| max < min_adjacent) {
+            return Err(format!("empty count range: {}", count));
+        }
+        Ok(Rule {
+            symbol,
+            min_adjacent,
+            max_adjacent,
+            aggregation: aggregation.parse()?,
+        })
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.max_adjacent {
+            Some(max) if max == self.min_adjacent => {
+                write!(f, "{}:{}:{}", self.symbol, max, self.aggregation)
+            }
+            Some(max) => write!(
+                f,
+                "{}:{}-{}:{}",
+                self.symbol, self.min_adjacent, max, self.aggregation
+            ),
+            None => write!(
+                f,
+                "{}:{}-:{}",
+                self.symbol, self.min_adjacent, self.aggregation
+            ),
+        }
+    }
+}
+
+/// Number of symbols a rule matched and the sum of their aggregated values.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct RuleTotal {
+    pub rule: Rule,
+    pub matches: usize,
+    pub total: u64,
+}
+
+pub fn apply_rules(
+    spans: &[PartSpan],
+    symbols: &HashMap<(usize, usize), char>,
+    rules: &[Rule],
+    neighborhood: &Neighborhood,
+) -> Result<Vec<RuleTotal>, OverflowError> {
+    let mut totals = rules
+        .iter()
+        .map(|rule| RuleTotal {
+            rule: *rule,
+            matches: 0,
+            total: 0,
+        })
+        .collect::<Vec<RuleTotal>>();
+    for ((line_i, row_i), c) in symbols {
+        let values = spans
+            .iter()
+            .filter(|span| span.touches(*line_i, *row_i, neighborhood))
+            .map(|span| span.value)
+            .collect::<Vec<u64>>();
+        for total in totals
+            .iter_mut()
+            .filter(|total| total.rule.matches(*c, values.len()))
+        {
+            total.matches += 1;
+            if let Some(val) = total.rule.aggregation.apply(&values)? {
+                total.total = checked_add(total.total, val)?;
+            }
+        }
+    }
+    Ok(totals)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day03::example_lines;
+    use crate::day03::symbols::SymbolSet;
+    use crate::day03::{get_part_spans, get_symbols};
+
+    fn totals(rules: &[Rule]) -> Vec<(usize, u64)> {
+        let lines = example_lines();
+        apply_rules(
+            &get_part_spans(&lines, &SymbolSet::default()).unwrap(),
+            &get_symbols(&lines, &SymbolSet::default()).unwrap(),
+            rules,
+            &Neighborhood::default(),
+        )
+        .unwrap()
+        .iter()
+        .map(|total| (total.matches, total.total))
+        .collect()
+    }
+
+    #[test]
+    fn test_apply_rules_gear_ratio() {
+        assert_eq!(totals(&[Rule::gear_ratio()]), vec![(2, 467835)])
+    }
+
+    #[test]
+    fn test_apply_rules_aggregations() {
+        assert_eq!(
+            totals(&[
+                "*:1-:sum".parse().unwrap(),
+                "*:1-:min".parse().unwrap(),
+                "*:1-:max".parse().unwrap(),
+                "*:0-:count".parse().unwrap(),
+                "#:1:product".parse().unwrap(),
+                "$:2-3:sum".parse().unwrap(),
+            ]),
+            vec![
+                (3, 467 + 35 + 617 + 755 + 598),
+                (3, 35 + 617 + 598),
+                (3, 467 + 617 + 755),
+                (3, 5),
+                (1, 633),
+                (0, 0),
+            ]
+        )
+    }
+
+    #[test]
+    fn test_rule_from_str() {
+        assert_eq!("*:2:product".parse::<Rule>(), Ok(Rule::gear_ratio()));
+        assert_eq!(
+            ":::1-:count".parse::<Rule>().map(|_| ()),
+            Err("expected symbol:count:aggregation but got: :::1-:count".to_string())
+        );
+        assert_eq!(
+            "::1-:count".parse::<Rule>(),
+            Ok(Rule {
+                symbol: ':',
+                min_adjacent: 1,
+                max_adjacent: None,
+                aggregation: Aggregation::Count,
+            })
+        );
+        assert!("#:x:sum".parse::<Rule>().is_err());
+        assert!("#:1:avg".parse::<Rule>().is_err());
+        assert_eq!(
+            "*:3-1:sum".parse::<Rule>(),
+            Err("empty count range: 3-1".to_string())
+        );
+        ["*:2:product", "#:1-3:sum", "$:0-:max"]
+            .iter()
+            .for_each(|rule| assert_eq!(&rule.parse::<Rule>().unwrap().to_string(), rule));
+    }
+
+    #[test]
+    fn test_aggregation_apply_overflow() {
+        assert_eq!(
+            Aggregation::Product.apply(&[u64::MAX, 2]),
+            Err(OverflowError::Mul(u64::MAX, 2))
+        );
+        assert_eq!(Aggregation::Min.apply(&[]), Ok(None));
+    }
+
+    #[test]
+    fn test_aggregation_apply_empty() {
+        assert_eq!(Aggregation::Product.apply(&[]), Ok(Some(0)));
+        assert_eq!(Aggregation::Sum.apply(&[]), Ok(Some(0)));
+        assert_eq!(Aggregation::Count.apply(&[]), Ok(Some(0)));
+        assert_eq!(Aggregation::Max.apply(&[]), Ok(None));
+    }
+}
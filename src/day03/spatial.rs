@@ -0,0 +1,346 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use super::graph::SymbolNode;
+use super::PartSpan;
+
+const BUCKET: usize = 16;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Metric {
+    Manhattan,
+    Chebyshev,
+}
+
+impl FromStr for Metric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Metric, String> {
+        match s {
+            "manhattan" => Ok(Metric::Manhattan),
+            "chebyshev" => Ok(Metric::Chebyshev),
+            _ => Err(format!("unknown metric: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Element {
+    Number(PartSpan),
+    Symbol(SymbolNode),
+}
+
+impl Element {
+    /// Line and first and last columns covered.
+    fn extent(&self) -> (usize, usize, usize) {
+        match self {
+            Element::Number(span) => (span.line, span.start, span.end - 1),
+            Element::Symbol(node) => (node.line, node.col, node.col),
+        }
+    }
+
+    /// Distance from (`line`, `col`) to the closest cell of the element.
+    pub fn distance(&self, line: usize, col: usize, metric: Metric) -> usize {
+        let (el_line, first, last) = self.extent();
+        let dl = el_line.abs_diff(line);
+        let dc = if col < first {
+            first - col
+        } else {
+            col.saturating_sub(last)
+        };
+        match metric {
+            Metric::Manhattan => dl + dc,
+            Metric::Chebyshev => dl.max(dc),
+        }
+    }
+
+    /// Whether any cell of the element lies in the inclusive rectangle.
+    pub fn intersects(&self, top: usize, left: usize, bottom: usize, right: usize) -> bool {
+        let (line, first, last) = self.extent();
+        top <= line && line <= bottom && first <= right && left <= last
+    }
+}
+
+/// Buckets schematic elements into fixed-size square tiles so that range and nearest-neighbor
+/// queries only look at tiles near the query point.
+#[derive(Debug, Clone)]
+pub struct SpatialIndex {
+    elements: Vec<Element>,
+    buckets: HashMap<(usize, usize), Vec<usize>>,
+    max_bucket: (usize, usize),
+}
+
+impl SpatialIndex {
+    pub fn new(spans: &[PartSpan], symbols: &HashMap<(usize, usize), char>) -> SpatialIndex {
+        let mut elements = spans
+            .iter()
+            .map(|span| Element::Number(*span))
+            .chain(symbols.iter().map(|((line, col), symbol)| {
+                Element::Symbol(SymbolNode {
+                    line: *line,
+                    col: *col,
+                    symbol: *symbol,
+                })
+            }))
+            .collect::<Vec<Element>>();
+        elements.sort_by_key(|element| element.extent());
+
+        let mut buckets: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        let mut max_bucket = (0, 0);
+        for (i, element) in elements.iter().enumerate() {
+            let (line, first, last) = element.extent();
+            for bucket_col in first / BUCKET..=last / BUCKET {
+                buckets
+                    .entry((line / BUCKET, bucket_col))
+                    .or_default()
+                    .push(i);
+                max_bucket = (
+                    max_bucket.0.max(line / BUCKET),
+                    max_bucket.1.max(bucket_col),
+                );
+            }
+        }
+
+        SpatialIndex {
+            elements,
+            buckets,
+            max_bucket,
+        }
+    }
+
+    /// Distinct element indexes in the buckets overlapping the inclusive cell rectangle.
+    fn candidates(&self, top: usize, left: usize, bottom: usize, right: usize) -> Vec<usize> {
+        let mut out = (top / BUCKET..=(bottom / BUCKET).min(self.max_bucket.0))
+            .flat_map(|bucket_line| {
+                (left / BUCKET..=(right / BUCKET).min(self.max_bucket.1))
+                    .filter_map(move |bucket_col| self.buckets.get(&(bucket_line, bucket_col)))
+            })
+            .flatten()
+            .copied()
+            .collect::<Vec<usize>>();
+        out.sort();
+        out.dedup();
+        out
+    }
+
+    /// Elements within distance `d` of (`line`, `col`), ordered by position.
+    pub fn within(&self, line: usize, col: usize, d: usize, metric: Metric) -> Vec<&Element> {
+        self.candidates(
+            line.saturating_sub(d),
+            col.saturating_sub(d),
+            line.saturating_add(d),
+            col.saturating_add(d),
+        )
+        .into_iter()
+        .map(|i| &self.elements[i])
+        .filter(|element| element.distance(line, col, metric) <= d)
+        .collect()
+    }
+
+    /// Elements with at least one cell in the inclusive rectangle, ordered by position.
+    pub fn in_rect(&self, top: usize, left: usize, bottom: usize, right: usize) -> Vec<&Element> {
+        self.candidates(top, left, bottom, right)
+            .into_iter()
+            .map(|i| &self.elements[i])
+            .filter(|element| element.intersects(top, left, bottom, right))
+            .collect()
+    }
+
+    /// The closest element accepted by `predicate` and its distance. Ties go to the element that
+    /// comes first by position.
+    pub fn nearest(
+        &self,
+        line: usize,
+        col: usize,
+        metric: Metric,
+        predicate: impl Fn(&Element) -> bool,
+    ) -> Option<(&Element, usize)> {
+        let (bucket_line, bucket_col) = (line / BUCKET, col / BUCKET);
+        let (max_line, max_col) = self.max_bucket;
+        // rings closer than `first` miss the bucket box entirely, and `last` covers all of it
+        let first = bucket_line
+            .saturating_sub(max_line)
+            .max(bucket_col.saturating_sub(max_col));
+        let last = bucket_line
+            .max(max_line.saturating_sub(bucket_line))
+            .max(bucket_col.max(max_col.saturating_sub(bucket_col)));
+        let mut best: Option<(usize, usize)> = None;
+        for ring in first..=last {
+            // every cell in ring `ring` or beyond is at least this far away
+            let bound = (ring * BUCKET).saturating_sub(BUCKET - 1);
+            if best.is_some_and(|(_, dist)| bound > dist) {
+                break;
+            }
+            for i in self.ring(bucket_line, bucket_col, ring) {
+                let element = &self.elements[i];
+                if !predicate(element) {
+                    continue;
+                }
+                let dist = element.distance(line, col, metric);
+                if best.is_none_or(|(best_i, best_dist)| (dist, i) < (best_dist, best_i)) {
+                    best = Some((i, dist));
+                }
+            }
+        }
+        best.map(|(i, dist)| (&self.elements[i], dist))
+    }
+
+    /// Element indexes in buckets exactly `ring` buckets away from the given bucket, visiting
+    /// only the part of the ring's perimeter inside the bucket box.
+    fn ring(&self, bucket_line: usize, bucket_col: usize, ring: usize) -> Vec<usize> {
+        let (max_line, max_col) = self.max_bucket;
+        let cols = bucket_col.saturating_sub(ring)..=(bucket_col + ring).min(max_col);
+        let mut buckets = vec![];
+        // top and bottom edges, then the left and right edges between them
+        let mut edge_lines = vec![];
+        if bucket_line >= ring {
+            edge_lines.push(bucket_line - ring);
+        }
+        if ring > 0 && bucket_line + ring <= max_line {
+            edge_lines.push(bucket_line + ring);
+        }
+        for l in edge_lines {
+            buckets.extend(cols.clone().map(|c| (l, c)));
+        }
+        if ring > 0 {
+            let mut edge_cols = vec![];
+            if bucket_col >= ring {
+                edge_cols.push(bucket_col - ring);
+            }
+            if bucket_col + ring <= max_col {
+                edge_cols.push(bucket_col + ring);
+            }
+            let lines =
+                (bucket_line + 1).saturating_sub(ring)..=(bucket_line + ring - 1).min(max_line);
+            for c in edge_cols {
+                buckets.extend(lines.clone().map(|l| (l, c)));
+            }
+        }
+        buckets
+            .iter()
+            .filter_map(|bucket| self.buckets.get(bucket))
+            .flatten()
+            .copied()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day03::symbols::SymbolSet;
+    use crate::day03::{get_part_spans, get_symbols};
+    use crate::rng::Rng;
+
+    fn random_index(seed: u64) -> (SpatialIndex, Vec<Element>) {
+        let mut rng = Rng::new(seed);
+        let lines = (0..60)
+            .map(|_| {
+                (0..70)
+                    .map(|_| match rng.range(0, 9) {
+                        0..=2 => (b'0' + rng.range(0, 9) as u8) as char,
+                        3 => ['*', '#', '$', '+'][rng.range(0, 3) as usize],
+                        _ => '.',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>();
+        let spans = get_part_spans(&lines, &SymbolSet::default()).unwrap();
+        let symbols = get_symbols(&lines, &SymbolSet::default()).unwrap();
+        let index = SpatialIndex::new(&spans, &symbols);
+        let elements = index.elements.clone();
+        (index, elements)
+    }
+
+    #[test]
+    fn test_within_matches_brute_force() {
+        let (index, elements) = random_index(1);
+        let mut rng = Rng::new(2);
+        for _ in 0..300 {
+            let line = rng.range(0, 70) as usize;
+            let col = rng.range(0, 80) as usize;
+            let d = rng.range(0, 20) as usize;
+            for metric in [Metric::Manhattan, Metric::Chebyshev] {
+                assert_eq!(
+                    index.within(line, col, d, metric),
+                    elements
+                        .iter()
+                        .filter(|element| element.distance(line, col, metric) <= d)
+                        .collect::<Vec<&Element>>()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_in_rect_matches_brute_force() {
+        let (index, elements) = random_index(3);
+        let mut rng = Rng::new(4);
+        for _ in 0..300 {
+            let top = rng.range(0, 60) as usize;
+            let left = rng.range(0, 70) as usize;
+            let bottom = top + rng.range(0, 30) as usize;
+            let right = left + rng.range(0, 30) as usize;
+            assert_eq!(
+                index.in_rect(top, left, bottom, right),
+                elements
+                    .iter()
+                    .filter(|element| element.intersects(top, left, bottom, right))
+                    .collect::<Vec<&Element>>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_nearest_matches_brute_force() {
+        let (index, elements) = random_index(5);
+        let mut rng = Rng::new(6);
+        let is_gear_symbol =
+            |element: &Element| matches!(element, Element::Symbol(node) if node.symbol == '*');
+        for _ in 0..300 {
+            let line = rng.range(0, 100) as usize;
+            let col = rng.range(0, 100) as usize;
+            for metric in [Metric::Manhattan, Metric::Chebyshev] {
+                let expected = elements
+                    .iter()
+                    .filter(|element| is_gear_symbol(element))
+                    .map(|element| (element.distance(line, col, metric), element))
+                    .min_by_key(|(dist, _)| *dist)
+                    .map(|(dist, element)| (element, dist));
+                assert_eq!(index.nearest(line, col, metric, is_gear_symbol), expected);
+            }
+        }
+        assert_eq!(index.nearest(0, 0, Metric::Manhattan, |_| false), None);
+    }
+
+    #[test]
+    fn test_nearest_far_away() {
+        let (index, elements) = random_index(7);
+        for (line, col) in [(20000, 0), (0, 1_000_000), (usize::MAX / 4, usize::MAX / 4)] {
+            for metric in [Metric::Manhattan, Metric::Chebyshev] {
+                let expected = elements
+                    .iter()
+                    .map(|element| (element.distance(line, col, metric), element))
+                    .min_by_key(|(dist, _)| *dist)
+                    .map(|(dist, element)| (element, dist));
+                assert_eq!(index.nearest(line, col, metric, |_| true), expected);
+            }
+        }
+        let empty = SpatialIndex::new(&[], &HashMap::new());
+        assert_eq!(empty.nearest(20000, 0, Metric::Chebyshev, |_| true), None);
+    }
+
+    #[test]
+    fn test_element_distance() {
+        let span = Element::Number(PartSpan {
+            line: 2,
+            start: 2,
+            end: 5,
+            value: 123,
+        });
+        assert_eq!(span.distance(2, 3, Metric::Manhattan), 0);
+        assert_eq!(span.distance(0, 0, Metric::Manhattan), 4);
+        assert_eq!(span.distance(0, 0, Metric::Chebyshev), 2);
+        assert_eq!(span.distance(3, 8, Metric::Chebyshev), 4);
+    }
+}
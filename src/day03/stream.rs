@@ -0,0 +1,274 @@
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+use super::symbols::{CellKind, SymbolSet};
+use super::{get_line_part_spans, PartSpan, SchematicError};
+use crate::arith::{checked_add, checked_mul};
+
+/// Something found once a row has both of its neighboring rows in the window.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Event {
+    PartNumber(PartSpan),
+    GearRatio { line: usize, col: usize, ratio: u64 },
+}
+
+/// The numbers and symbols of one row.
+#[derive(Debug)]
+struct Row {
+    line: usize,
+    spans: Vec<PartSpan>,
+    symbols: Vec<(usize, char)>,
+}
+
+/// Solves a schematic row by row, holding at most three rows at a time, so memory grows with the
+/// width of the schematic but not its height. Adjacency is the default 8-way neighborhood.
+#[derive(Debug)]
+pub struct StreamingSolver {
+    symbol_set: SymbolSet,
+    window: VecDeque<Row>,
+    line: usize,
+}
+
+impl StreamingSolver {
+    pub fn new(symbol_set: SymbolSet) -> StreamingSolver {
+        StreamingSolver {
+            symbol_set,
+            window: VecDeque::with_capacity(3),
+            line: 0,
+        }
+    }
+
+    /// Add the next row, returning the events of the row before it, which is now complete.
+    pub fn push_line(&mut self, line: &str) -> Result<Vec<Event>, SchematicError> {
+        let row = self.parse_row(line)?;
+        self.line += 1;
+        self.window.push_back(row);
+        if self.window.len() > 3 {
+            self.window.pop_front();
+        }
+        match self.window.len() {
+            1 => Ok(Vec::new()),
+            len => self.process(len - 2),
+        }
+    }
+
+    /// Flush the events of the last row.
+    pub fn finish(self) -> Result<Vec<Event>, SchematicError> {
+        match self.window.len() {
+            0 => Ok(Vec::new()),
+            len => self.process(len - 1),
+        }
+    }
+
+    fn parse_row(&self, line: &str) -> Result<Row, SchematicError> {
+        let mut symbols = Vec::new();
+        for (row_i, c) in line.chars().enumerate() {
+            match self.symbol_set.classify(c) {
+                Some(CellKind::Symbol) => symbols.push((row_i, c)),
+                Some(_) => (),
+                None => {
+                    return Err(SchematicError::UnknownChar {
+                        line: self.line,
+                        col: row_i,
+                        c,
+                    })
+                }
+            }
+        }
+        let spans = get_line_part_spans(self.line, line, &self.symbol_set)?;
+        Ok(Row {
+            line: self.line,
+            spans,
+            symbols,
+        })
+    }
+
+    /// Events for the row at `middle` of the window, using whichever rows either side of it are
+    /// present.
+    fn process(&self, middle: usize) -> Result<Vec<Event>, SchematicError> {
+        let row = &self.window[middle];
+        let neighbors = self
+            .window
+            .iter()
+            .filter(|other| other.line.abs_diff(row.line) <= 1)
+            .collect::<Vec<&Row>>();
+
+        let mut events = row
+            .spans
+            .iter()
+            .filter(|span| {
+                neighbors.iter().any(|other| {
+                    other
+                        .symbols
+                        .iter()
+                        .any(|(col, _)| span.is_adjacent(other.line, *col))
+                })
+            })
+            .map(|span| Event::PartNumber(*span))
+            .collect::<Vec<Event>>();
+
+        for (col, c) in &row.symbols {
+            if *c != '*' {
+                continue;
+            }
+            let adjacent = neighbors
+                .iter()
+                .flat_map(|other| other.spans.iter())
+                .filter(|span| span.is_adjacent(row.line, *col))
+                .collect::<Vec<&PartSpan>>();
+            if let [a, b] = adjacent[..] {
+                events.push(Event::GearRatio {
+                    line: row.line,
+                    col: *col,
+                    ratio: checked_mul(a.value, b.value)?,
+                });
+            }
+        }
+        Ok(events)
+    }
+}
+
+/// Stream `input`, passing every event to `on_event`, and return the part number sum and the gear
+/// ratio sum.
+pub fn solve_stream<R: BufRead>(
+    input: R,
+    symbol_set: SymbolSet,
+    mut on_event: impl FnMut(&Event),
+) -> Result<(u64, u64), SchematicError> {
+    let mut solver = StreamingSolver::new(symbol_set);
+    let mut sums = (0u64, 0u64);
+    let mut handle = |events: Vec<Event>| -> Result<(), SchematicError> {
+        for event in events {
+            on_event(&event);
+            match event {
+                Event::PartNumber(span) => sums.0 = checked_add(sums.0, span.value)?,
+                Event::GearRatio { ratio, .. } => sums.1 = checked_add(sums.1, ratio)?,
+            }
+        }
+        Ok(())
+    };
+    for line in input.lines() {
+        let line = line.map_err(|err| SchematicError::Read(err.to_string()))?;
+        if !line.is_empty() {
+            handle(solver.push_line(&line)?)?;
+        }
+    }
+    handle(solver.finish()?)?;
+    Ok(sums)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::arith::checked_sum;
+    use crate::day03::neighborhood::Neighborhood;
+    use crate::day03::EXAMPLE;
+    use crate::day03::{get_gear_ratios, get_part_nums, get_symbols, get_unique_part_numbers};
+
+    fn batch(input: &str) -> (u64, u64) {
+        let lines = input
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| line.to_string())
+            .collect::<Vec<String>>();
+        let part_nums = get_part_nums(&lines, &SymbolSet::default()).unwrap();
+        let symbols = get_symbols(&lines, &SymbolSet::default()).unwrap();
+        (
+            checked_sum(get_unique_part_numbers(
+                &part_nums,
+                &symbols,
+                &Neighborhood::default(),
+            ))
+            .unwrap(),
+            checked_sum(get_gear_ratios(&part_nums, &symbols, &Neighborhood::default()).unwrap())
+                .unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_solve_stream() {
+        let input = EXAMPLE;
+        let mut events = Vec::new();
+        assert_eq!(
+            solve_stream(input.as_bytes(), SymbolSet::default(), |event| events
+                .push(*event)),
+            Ok((4361, 467835))
+        );
+        assert_eq!(batch(input), (4361, 467835));
+        assert_eq!(
+            events
+                .iter()
+                .filter_map(|event| match event {
+                    Event::GearRatio { line, col, ratio } => Some((*line, *col, *ratio)),
+                    _ => None,
+                })
+                .collect::<Vec<(usize, usize, u64)>>(),
+            vec![(1, 3, 16345), (8, 5, 451490)]
+        );
+    }
+
+    #[test]
+    fn test_solve_stream_unreadable() {
+        assert_eq!(
+            solve_stream(&b"12*\n\xff.."[..], SymbolSet::default(), |_| ()),
+            Err(SchematicError::Read(
+                "stream did not contain valid UTF-8".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_solve_stream_edges() {
+        [
+            "",
+            "12*3",
+            "1\n*\n2",
+            "*12\n...\n...\n12*",
+            "..5..\n.....\n3*4\n.....\n..6..",
+            "7.7\n.*.\n7.7",
+        ]
+        .iter()
+        .for_each(|input| {
+            assert_eq!(
+                solve_stream(input.as_bytes(), SymbolSet::default(), |_| ()),
+                Ok(batch(input)),
+                "{}",
+                input
+            )
+        });
+    }
+
+    #[test]
+    fn test_solve_stream_shared_number() {
+        // a number touching two symbols is still one part number, in both solvers
+        ["*12#", "*...\n.12.\n...#"].iter().for_each(|input| {
+            assert_eq!(
+                solve_stream(input.as_bytes(), SymbolSet::default(), |_| ()),
+                Ok((12, 0)),
+                "{}",
+                input
+            );
+            assert_eq!(batch(input), (12, 0), "{}", input);
+        });
+    }
+
+    #[test]
+    fn test_push_line_window() {
+        let mut solver = StreamingSolver::new(SymbolSet::default());
+        assert_eq!(solver.push_line("1.."), Ok(vec![]));
+        assert_eq!(
+            solver.push_line(".*."),
+            Ok(vec![Event::PartNumber(PartSpan {
+                line: 0,
+                start: 0,
+                end: 1,
+                value: 1
+            })])
+        );
+        assert_eq!(solver.window.len(), 2);
+        solver.push_line("...").unwrap();
+        solver.push_line("...").unwrap();
+        assert_eq!(solver.window.len(), 3);
+        assert_eq!(solver.finish(), Ok(vec![]));
+    }
+}
@@ -0,0 +1,167 @@
+use super::neighborhood::Neighborhood;
+use super::symbols::{CellKind, SymbolSet};
+use super::{get_gears, get_part_spans, get_symbols, is_part_number, PartSpan, SchematicError};
+
+const CELL: usize = 16;
+
+fn escape(c: char) -> String {
+    match c {
+        '&' => "&amp;".to_string(),
+        '<' => "&lt;".to_string(),
+        '>' => "&gt;".to_string(),
+        '"' => "&quot;".to_string(),
+        '\'' => "&apos;".to_string(),
+        c => c.to_string(),
+    }
+}
+
+/// Center of the cell at (`line`, `col`) in SVG coordinates.
+fn center(line: usize, col: usize) -> (usize, usize) {
+    (col * CELL + CELL / 2, line * CELL + CELL / 2)
+}
+
+fn span_center(span: &PartSpan) -> (usize, usize) {
+    (
+        (span.start + span.end) * CELL / 2,
+        span.line * CELL + CELL / 2,
+    )
+}
+
+/// Draw a schematic as a standalone SVG document. Part numbers touching a symbol are shaded green
+/// and lone numbers red; each gear is joined to its two numbers and labelled with its ratio.
+///
+/// Text uses the generic `monospace` family so no font has to be embedded or fetched.
+pub fn to_svg(lines: &[String], symbol_set: &SymbolSet) -> Result<String, SchematicError> {
+    let spans = get_part_spans(lines, symbol_set)?;
+    let symbols = get_symbols(lines, symbol_set)?;
+    let neighborhood = Neighborhood::default();
+    let gears = get_gears(&spans, &symbols, &neighborhood);
+    let width = lines
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0)
+        * CELL;
+    let height = lines.len() * CELL;
+
+    let mut out = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n",
+        w = width,
+        h = height
+    );
+    out.push_str(&format!(
+        "<rect x=\"0\" y=\"0\" width=\"{}\" height=\"{}\" fill=\"#ffffff\"/>\n",
+        width, height
+    ));
+
+    // grid
+    out.push_str("<g stroke=\"#e0e0e0\" stroke-width=\"1\">\n");
+    (0..=lines.len()).for_each(|line_i| {
+        out.push_str(&format!(
+            "<line x1=\"0\" y1=\"{y}\" x2=\"{}\" y2=\"{y}\"/>\n",
+            width,
+            y = line_i * CELL
+        ))
+    });
+    (0..=width / CELL).for_each(|row_i| {
+        out.push_str(&format!(
+            "<line x1=\"{x}\" y1=\"0\" x2=\"{x}\" y2=\"{}\"/>\n",
+            height,
+            x = row_i * CELL
+        ))
+    });
+    out.push_str("</g>\n");
+
+    // part number shading
+    out.push_str("<g>\n");
+    spans.iter().for_each(|span| {
+        let fill = if is_part_number(span, &symbols, &neighborhood) {
+            "#b7e4b0"
+        } else {
+            "#f4b6b6"
+        };
+        out.push_str(&format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>\n",
+            span.start * CELL,
+            span.line * CELL,
+            (span.end - span.start) * CELL,
+            CELL,
+            fill
+        ))
+    });
+    out.push_str("</g>\n");
+
+    // cell contents
+    out.push_str(&format!(
+        "<g font-family=\"monospace\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\">\n",
+        CELL * 3 / 4
+    ));
+    for (line_i, line) in lines.iter().enumerate() {
+        for (row_i, c) in line.chars().enumerate() {
+            if symbol_set.classify(c) == Some(CellKind::Blank) {
+                continue;
+            }
+            let (x, y) = center(line_i, row_i);
+            out.push_str(&format!(
+                "<text x=\"{}\" y=\"{}\">{}</text>\n",
+                x,
+                y,
+                escape(c)
+            ));
+        }
+    }
+    out.push_str("</g>\n");
+
+    // gear links and ratios
+    out.push_str("<g stroke=\"#d08000\" stroke-width=\"2\">\n");
+    gears.iter().for_each(|gear| {
+        let (x1, y1) = center(gear.line, gear.col);
+        gear.parts.iter().for_each(|span| {
+            let (x2, y2) = span_center(span);
+            out.push_str(&format!(
+                "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\"/>\n",
+                x1, y1, x2, y2
+            ));
+        });
+    });
+    out.push_str("</g>\n");
+    out.push_str(&format!(
+        "<g font-family=\"monospace\" font-size=\"{}\" fill=\"#d08000\">\n",
+        CELL / 2
+    ));
+    for gear in &gears {
+        let (x, y) = center(gear.line, gear.col);
+        out.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\">{}</text>\n",
+            x + CELL / 2,
+            y - CELL / 2,
+            gear.ratio()?
+        ));
+    }
+    out.push_str("</g>\n</svg>\n");
+    Ok(out)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day03::example_lines;
+
+    #[test]
+    fn test_to_svg() {
+        let lines = example_lines();
+        assert_eq!(
+            to_svg(&lines, &SymbolSet::default()),
+            Ok(include_str!("snapshots/schematic.svg").to_string())
+        )
+    }
+
+    #[test]
+    fn test_to_svg_escapes() {
+        let svg = to_svg(&["1&<2".to_string()], &SymbolSet::default()).unwrap();
+        assert!(svg.contains(">&amp;</text>"));
+        assert!(svg.contains(">&lt;</text>"));
+        assert!(!svg.contains(">&<"));
+    }
+}
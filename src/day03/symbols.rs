@@ -0,0 +1,122 @@
+use std::collections::BTreeSet;
+
+use super::SchematicError;
+
+/// What a single schematic cell holds.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CellKind {
+    Symbol,
+    Blank,
+    Digit,
+}
+
+/// Which characters of a schematic count as symbols, blanks and digits.
+///
+/// The default matches the puzzle: `.` is blank, `0`-`9` are digits and any other ASCII
+/// punctuation is a symbol.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SymbolSet {
+    symbols: BTreeSet<char>,
+    blanks: BTreeSet<char>,
+    digits: BTreeSet<char>,
+}
+
+impl SymbolSet {
+    pub fn new(symbols: &str, blanks: &str, digits: &str) -> Result<SymbolSet, SchematicError> {
+        let symbols = symbols.chars().collect::<BTreeSet<char>>();
+        let blanks = blanks.chars().collect::<BTreeSet<char>>();
+        let digits = digits.chars().collect::<BTreeSet<char>>();
+
+        if let Some(c) = digits.iter().find(|c| !c.is_ascii_digit()) {
+            return Err(SchematicError::InvalidSymbolSet(format!(
+                "digit {:?} is not an ASCII digit",
+                c
+            )));
+        }
+        if let Some(c) = symbols
+            .intersection(&blanks)
+            .chain(symbols.intersection(&digits))
+            .chain(blanks.intersection(&digits))
+            .next()
+        {
+            return Err(SchematicError::InvalidSymbolSet(format!(
+                "{:?} belongs to more than one class",
+                c
+            )));
+        }
+
+        Ok(SymbolSet {
+            symbols,
+            blanks,
+            digits,
+        })
+    }
+
+    pub fn classify(&self, c: char) -> Option<CellKind> {
+        if self.symbols.contains(&c) {
+            Some(CellKind::Symbol)
+        } else if self.blanks.contains(&c) {
+            Some(CellKind::Blank)
+        } else if self.digits.contains(&c) {
+            Some(CellKind::Digit)
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for SymbolSet {
+    fn default() -> SymbolSet {
+        let symbols = (0..128u8)
+            .map(|c| c as char)
+            .filter(|c| c.is_ascii_punctuation() && *c != '.')
+            .collect::<String>();
+        SymbolSet::new(&symbols, ".", "0123456789").expect("default symbol set is valid")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day03::get_line_part_spans;
+
+    #[test]
+    fn test_default_classify() {
+        let symbol_set = SymbolSet::default();
+        assert_eq!(symbol_set.classify('*'), Some(CellKind::Symbol));
+        assert_eq!(symbol_set.classify('-'), Some(CellKind::Symbol));
+        assert_eq!(symbol_set.classify('.'), Some(CellKind::Blank));
+        assert_eq!(symbol_set.classify('7'), Some(CellKind::Digit));
+        assert_eq!(symbol_set.classify('a'), None);
+        assert_eq!(symbol_set.classify('é'), None);
+    }
+
+    #[test]
+    fn test_new_invalid() {
+        assert_eq!(
+            SymbolSet::new("*", "*", "0123456789"),
+            Err(SchematicError::InvalidSymbolSet(
+                "'*' belongs to more than one class".to_string()
+            ))
+        );
+        assert_eq!(
+            SymbolSet::new("*", ".", "x"),
+            Err(SchematicError::InvalidSymbolSet(
+                "digit 'x' is not an ASCII digit".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_custom_digits() {
+        let symbol_set = SymbolSet::new("#", ".-", "123").unwrap();
+        assert_eq!(
+            get_line_part_spans(0, "12.40-331", &symbol_set)
+                .unwrap()
+                .iter()
+                .map(|span| span.value)
+                .collect::<Vec<u64>>(),
+            vec![12, 331]
+        );
+    }
+}
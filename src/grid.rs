@@ -0,0 +1,240 @@
+use std::fmt;
+use std::io::Read;
+
+/// What to do with rows shorter than the widest row.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RaggedPolicy {
+    /// Pad short rows on the right with the given character.
+    Pad(char),
+    /// Fail if rows differ in width.
+    Reject,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct GridOptions {
+    pub ragged: RaggedPolicy,
+    /// Tabs are expanded to the next multiple of this width using `fill`.
+    pub tab_width: usize,
+    /// Character used when expanding tabs.
+    pub fill: char,
+}
+
+impl Default for GridOptions {
+    fn default() -> GridOptions {
+        GridOptions {
+            ragged: RaggedPolicy::Pad('.'),
+            tab_width: 4,
+            fill: '.',
+        }
+    }
+}
+
+/// A rectangular grid of text. `widths` holds the width each row had before any padding.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Grid {
+    pub lines: Vec<String>,
+    pub widths: Vec<usize>,
+    pub width: usize,
+}
+
+impl Grid {
+    /// Runs of consecutive rows with the same original width, as (first row, last row, width).
+    pub fn width_runs(&self) -> Vec<(usize, usize, usize)> {
+        let mut out: Vec<(usize, usize, usize)> = Vec::new();
+        for (line_i, width) in self.widths.iter().enumerate() {
+            match out.last_mut() {
+                Some((_, last, run_width)) if run_width == width => *last = line_i,
+                _ => out.push((line_i, line_i, *width)),
+            }
+        }
+        out
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum GridError {
+    /// Rows differ in width; holds the width of every row.
+    Ragged { widths: Vec<usize> },
+    /// Tabs cannot be expanded to a width of zero.
+    ZeroTabWidth,
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::Ragged { widths } => {
+                let expected = widths.iter().copied().max().unwrap_or(0);
+                write!(
+                    f,
+                    "rows are narrower than the widest row ({}): {}",
+                    expected,
+                    widths
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, width)| **width != expected)
+                        .map(|(line_i, width)| format!("line {} has width {}", line_i, width))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                )
+            }
+            GridError::ZeroTabWidth => write!(f, "tab width must be at least 1"),
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+fn normalize_line(line: &str, options: &GridOptions) -> String {
+    let mut out = String::new();
+    let mut width = 0;
+    for c in line.trim_end().chars() {
+        if c == '\t' {
+            let pad = options.tab_width - width % options.tab_width;
+            (0..pad).for_each(|_| out.push(options.fill));
+            width += pad;
+        } else {
+            out.push(c);
+            width += 1;
+        }
+    }
+    out
+}
+
+/// Split `text` into grid rows, accepting `\n`, `\r\n` and `\r` line endings, dropping trailing
+/// whitespace, expanding tabs and skipping empty rows, then apply the ragged row policy.
+pub fn parse_grid(text: &str, options: &GridOptions) -> Result<Grid, GridError> {
+    if options.tab_width == 0 {
+        return Err(GridError::ZeroTabWidth);
+    }
+    let lines = text
+        .replace("\r\n", "\n")
+        .split(['\n', '\r'])
+        .map(|line| normalize_line(line, options))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<String>>();
+    let widths = lines
+        .iter()
+        .map(|line| line.chars().count())
+        .collect::<Vec<usize>>();
+    let width = widths.iter().copied().max().unwrap_or(0);
+
+    let lines = match options.ragged {
+        RaggedPolicy::Reject if widths.iter().any(|val| *val != width) => {
+            return Err(GridError::Ragged { widths });
+        }
+        RaggedPolicy::Reject => lines,
+        RaggedPolicy::Pad(pad) => lines
+            .into_iter()
+            .zip(widths.iter())
+            .map(|(mut line, line_width)| {
+                (*line_width..width).for_each(|_| line.push(pad));
+                line
+            })
+            .collect(),
+    };
+    Ok(Grid {
+        lines,
+        widths,
+        width,
+    })
+}
+
+pub fn read_grid<R: Read>(mut input: R, options: &GridOptions) -> Result<Grid, GridError> {
+    let mut text = String::new();
+    input
+        .read_to_string(&mut text)
+        .expect("Unable to read input");
+    parse_grid(&text, options)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_grid_line_endings() {
+        assert_eq!(
+            parse_grid("12.\r\n.*.\r..3\n", &GridOptions::default()),
+            Ok(Grid {
+                lines: vec!["12.".to_string(), ".*.".to_string(), "..3".to_string()],
+                widths: vec![3, 3, 3],
+                width: 3,
+            })
+        )
+    }
+
+    #[test]
+    fn test_parse_grid_whitespace_and_tabs() {
+        assert_eq!(
+            parse_grid("1\t*  \n12345678 \t\n", &GridOptions::default()),
+            Ok(Grid {
+                lines: vec!["1...*...".to_string(), "12345678".to_string()],
+                widths: vec![5, 8],
+                width: 8,
+            })
+        )
+    }
+
+    #[test]
+    fn test_parse_grid_pad() {
+        let grid = parse_grid(
+            "467..\n...*.\n..35\n",
+            &GridOptions {
+                ragged: RaggedPolicy::Pad('_'),
+                ..GridOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(grid.lines, vec!["467..", "...*.", "..35_"]);
+        assert_eq!(grid.widths, vec![5, 5, 4]);
+        assert_eq!(grid.width_runs(), vec![(0, 1, 5), (2, 2, 4)]);
+    }
+
+    #[test]
+    fn test_parse_grid_reject() {
+        let err = parse_grid(
+            "467..\n...*.\n..35\n",
+            &GridOptions {
+                ragged: RaggedPolicy::Reject,
+                ..GridOptions::default()
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            GridError::Ragged {
+                widths: vec![5, 5, 4]
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "rows are narrower than the widest row (5): line 2 has width 4"
+        );
+    }
+
+    #[test]
+    fn test_parse_grid_non_ascii() {
+        assert_eq!(
+            parse_grid("é1\n·\n", &GridOptions::default()),
+            Ok(Grid {
+                lines: vec!["é1".to_string(), "·.".to_string()],
+                widths: vec![2, 1],
+                width: 2,
+            })
+        )
+    }
+
+    #[test]
+    fn test_parse_grid_zero_tab_width() {
+        assert_eq!(
+            parse_grid(
+                "1\t*",
+                &GridOptions {
+                    tab_width: 0,
+                    ..GridOptions::default()
+                }
+            ),
+            Err(GridError::ZeroTabWidth)
+        );
+    }
+}
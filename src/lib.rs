@@ -0,0 +1,16 @@
+#[macro_use]
+extern crate maplit;
+
+pub mod answers;
+pub mod arith;
+pub mod cli;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod grid;
+pub mod parse;
+pub mod reference;
+pub mod rng;
+pub mod runner;
+pub mod scaffold;
+pub mod watch;
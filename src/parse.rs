@@ -0,0 +1,464 @@
+//! Small parser combinators for puzzle input formats.
+//!
+//! A parser is any `Fn(Input) -> ParseResult<T>`: it either consumes a prefix of the input and
+//! returns a value with the remaining input, or fails with the offset at which it got stuck.
+
+use std::fmt;
+
+use crate::arith::{parse_u64, OverflowError};
+
+/// The unparsed remainder of a string, remembering how far into the original it starts.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Input<'a> {
+    src: &'a str,
+    pos: usize,
+    col: usize,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(src: &'a str) -> Input<'a> {
+        Input {
+            src,
+            pos: 0,
+            col: 0,
+        }
+    }
+
+    pub fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    /// Byte offset of the remaining input into the original string.
+    pub fn offset(&self) -> usize {
+        self.pos
+    }
+
+    /// Character offset of the remaining input into the original string.
+    pub fn column(&self) -> usize {
+        self.col
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos == self.src.len()
+    }
+
+    fn advance(self, len: usize) -> Input<'a> {
+        Input {
+            src: self.src,
+            pos: self.pos + len,
+            col: self.col + self.rest()[..len].chars().count(),
+        }
+    }
+
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        let found = match self.rest().char_indices().nth(10) {
+            _ if self.is_empty() => "end of input".to_string(),
+            Some((i, _)) => format!("{:?}...", &self.rest()[..i]),
+            None => format!("{:?}", self.rest()),
+        };
+        ParseError {
+            offset: self.pos,
+            column: self.col,
+            kind,
+            found,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParseErrorKind {
+    /// The input did not match; holds a description of what would have.
+    Expected(String),
+    /// A number matched but does not fit in its type.
+    Overflow(OverflowError),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseError {
+    /// Byte offset into the input.
+    pub offset: usize,
+    /// Character offset into the input, for error messages.
+    pub column: usize,
+    pub kind: ParseErrorKind,
+    /// The start of the input at `offset`, for error messages.
+    pub found: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ParseErrorKind::Expected(expected) => write!(
+                f,
+                "expected {} at column {} but found {}",
+                expected, self.column, self.found
+            ),
+            ParseErrorKind::Overflow(err) => write!(f, "{} at column {}", err, self.column),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub type ParseResult<'a, T> = Result<(T, Input<'a>), ParseError>;
+
+pub trait Parser<'a, T>: Fn(Input<'a>) -> ParseResult<'a, T> {}
+
+impl<'a, T, F: Fn(Input<'a>) -> ParseResult<'a, T>> Parser<'a, T> for F {}
+
+/// Character columns `start..end` of the original input.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Exactly `expected`.
+pub fn literal<'a>(expected: &'static str) -> impl Parser<'a, &'a str> {
+    move |input: Input<'a>| match input.rest().strip_prefix(expected) {
+        Some(_) => Ok((
+            &input.rest()[..expected.len()],
+            input.advance(expected.len()),
+        )),
+        None => Err(input.error(ParseErrorKind::Expected(format!("{:?}", expected)))),
+    }
+}
+
+/// The longest non-empty prefix whose characters all satisfy `pred`; `what` describes one such
+/// character for error messages.
+pub fn take_while1<'a>(
+    pred: impl Fn(char) -> bool,
+    what: &'static str,
+) -> impl Parser<'a, &'a str> {
+    move |input: Input<'a>| {
+        let len = input
+            .rest()
+            .char_indices()
+            .find(|(_, c)| !pred(*c))
+            .map_or(input.rest().len(), |(i, _)| i);
+        if len == 0 {
+            Err(input.error(ParseErrorKind::Expected(what.to_string())))
+        } else {
+            Ok((&input.rest()[..len], input.advance(len)))
+        }
+    }
+}
+
+/// Zero or more spaces and tabs.
+pub fn whitespace<'a>() -> impl Parser<'a, &'a str> {
+    move |input: Input<'a>| {
+        let len = input.rest().len() - input.rest().trim_start_matches([' ', '\t']).len();
+        Ok((&input.rest()[..len], input.advance(len)))
+    }
+}
+
+/// `parser`, ignoring whitespace on either side.
+pub fn ws<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    move |input: Input<'a>| {
+        let (_, input) = whitespace()(input)?;
+        let (value, input) = parser(input)?;
+        let (_, input) = whitespace()(input)?;
+        Ok((value, input))
+    }
+}
+
+/// A run of ASCII letters.
+pub fn word<'a>() -> impl Parser<'a, &'a str> {
+    take_while1(|c| c.is_ascii_alphabetic(), "a letter")
+}
+
+/// A run of ASCII digits, without sign.
+pub fn unsigned<'a>() -> impl Parser<'a, u64> {
+    move |input: Input<'a>| {
+        let (digits, rest) = take_while1(|c| c.is_ascii_digit(), "a digit")(input)?;
+        match parse_u64(digits) {
+            Ok(value) => Ok((value, rest)),
+            Err(err) => Err(input.error(ParseErrorKind::Overflow(err))),
+        }
+    }
+}
+
+/// A run of ASCII digits with an optional leading `-`.
+pub fn signed<'a>() -> impl Parser<'a, i64> {
+    move |input: Input<'a>| {
+        let negative = input.rest().starts_with('-');
+        let after_sign = input.advance(if negative { 1 } else { 0 });
+        let (digits, rest) = take_while1(|c| c.is_ascii_digit(), "a digit")(after_sign)?;
+        let number = &input.rest()[..rest.offset() - input.offset()];
+        match number.parse::<i64>() {
+            Ok(value) => Ok((value, rest)),
+            Err(_) => Err(input.error(ParseErrorKind::Overflow(OverflowError::Parse(
+                if negative { number } else { digits }.to_string(),
+            )))),
+        }
+    }
+}
+
+/// One or more `item`s separated by `separator`.
+pub fn separated<'a, T, S>(
+    item: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+) -> impl Parser<'a, Vec<T>> {
+    move |input: Input<'a>| {
+        let (first, mut input) = item(input)?;
+        let mut out = vec![first];
+        while let Ok((_, after_separator)) = separator(input) {
+            let (next, rest) = item(after_separator)?;
+            out.push(next);
+            input = rest;
+        }
+        Ok((out, input))
+    }
+}
+
+/// `first`, then `separator`, then `second`.
+pub fn pair<'a, A, S, B>(
+    first: impl Parser<'a, A>,
+    separator: impl Parser<'a, S>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, (A, B)> {
+    move |input: Input<'a>| {
+        let (a, input) = first(input)?;
+        let (_, input) = separator(input)?;
+        let (b, input) = second(input)?;
+        Ok(((a, b), input))
+    }
+}
+
+/// `inner` between `open` and `close`, e.g. a quoted string.
+pub fn delimited<'a, O, T, C>(
+    open: impl Parser<'a, O>,
+    inner: impl Parser<'a, T>,
+    close: impl Parser<'a, C>,
+) -> impl Parser<'a, T> {
+    move |input: Input<'a>| {
+        let (_, input) = open(input)?;
+        let (value, input) = inner(input)?;
+        let (_, input) = close(input)?;
+        Ok((value, input))
+    }
+}
+
+/// A double-quoted string in which `\"`, `\\` and `\n` stand for a quote, a backslash and a
+/// newline.
+pub fn quoted<'a>() -> impl Parser<'a, String> {
+    move |input: Input<'a>| {
+        let (_, mut input) = literal("\"")(input)?;
+        let mut out = String::new();
+        loop {
+            let mut chars = input.rest().chars();
+            match chars.next() {
+                Some('"') => return Ok((out, input.advance(1))),
+                Some('\\') => {
+                    match chars.next() {
+                        Some('"') => out.push('"'),
+                        Some('\\') => out.push('\\'),
+                        Some('n') => out.push('\n'),
+                        _ => {
+                            return Err(input
+                                .error(ParseErrorKind::Expected("an escape sequence".to_string())))
+                        }
+                    }
+                    input = input.advance(2);
+                }
+                Some(c) => {
+                    out.push(c);
+                    input = input.advance(c.len_utf8());
+                }
+                None => return Err(input.error(ParseErrorKind::Expected(format!("{:?}", "\"")))),
+            }
+        }
+    }
+}
+
+/// A word naming a key, then `separator` (whitespace around it is allowed), then `value`, e.g.
+/// `key_value("=", unsigned())` for `red = 12`.
+pub fn key_value<'a, T>(
+    separator: &'static str,
+    value: impl Parser<'a, T>,
+) -> impl Parser<'a, (&'a str, T)> {
+    pair(word(), ws(literal(separator)), value)
+}
+
+pub fn map<'a, T, U>(parser: impl Parser<'a, T>, f: impl Fn(T) -> U) -> impl Parser<'a, U> {
+    move |input: Input<'a>| parser(input).map(|(value, rest)| (f(value), rest))
+}
+
+/// `parser`'s value along with the span of input it consumed.
+pub fn spanned<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, (T, Span)> {
+    move |input: Input<'a>| {
+        let (value, rest) = parser(input)?;
+        Ok((
+            (
+                value,
+                Span {
+                    start: input.column(),
+                    end: rest.column(),
+                },
+            ),
+            rest,
+        ))
+    }
+}
+
+/// Succeeds only when there is no input left.
+pub fn end<'a>() -> impl Parser<'a, ()> {
+    move |input: Input<'a>| {
+        if input.is_empty() {
+            Ok(((), input))
+        } else {
+            Err(input.error(ParseErrorKind::Expected("end of input".to_string())))
+        }
+    }
+}
+
+/// Run `parser` over all of `src`, failing if anything is left over.
+pub fn parse_all<'a, T>(parser: impl Parser<'a, T>, src: &'a str) -> Result<T, ParseError> {
+    let (value, rest) = parser(Input::new(src))?;
+    end()(rest)?;
+    Ok(value)
+}
+
+/// Every non-overlapping match of `parser` in `src`, scanning left to right and skipping a
+/// character wherever it does not match. Errors other than a mismatch, such as a number that is
+/// too large, stop the scan rather than being skipped past.
+pub fn find_all<'a, T>(parser: impl Parser<'a, T>, src: &'a str) -> Result<Vec<T>, ParseError> {
+    let mut out = vec![];
+    let mut input = Input::new(src);
+    while !input.is_empty() {
+        match parser(input) {
+            Ok((value, rest)) if rest.offset() > input.offset() => {
+                out.push(value);
+                input = rest;
+            }
+            Err(err) if !matches!(err.kind, ParseErrorKind::Expected(_)) => return Err(err),
+            _ => input = input.advance(input.rest().chars().next().unwrap().len_utf8()),
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_literal() {
+        assert_eq!(parse_all(literal("Game"), "Game"), Ok("Game"));
+        assert_eq!(
+            parse_all(literal("Game"), "Gaem 1").map_err(|err| err.to_string()),
+            Err("expected \"Game\" at column 0 but found \"Gaem 1\"".to_string())
+        );
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(parse_all(unsigned(), "18446744073709551615"), Ok(u64::MAX));
+        assert_eq!(
+            parse_all(unsigned(), "18446744073709551616").map_err(|err| err.to_string()),
+            Err("number too large: 18446744073709551616 at column 0".to_string())
+        );
+        assert_eq!(parse_all(signed(), "-42"), Ok(-42));
+        assert_eq!(parse_all(signed(), "42"), Ok(42));
+        assert_eq!(parse_all(signed(), "-9223372036854775808"), Ok(i64::MIN));
+        assert_eq!(
+            parse_all(signed(), "-x").map_err(|err| err.to_string()),
+            Err("expected a digit at column 1 but found \"x\"".to_string())
+        );
+        assert_eq!(
+            parse_all(unsigned(), "12a").map_err(|err| err.to_string()),
+            Err("expected end of input at column 2 but found \"a\"".to_string())
+        );
+    }
+
+    #[test]
+    fn test_separated() {
+        let list = separated(ws(unsigned()), literal(","));
+        assert_eq!(parse_all(&list, "1, 2 ,3"), Ok(vec![1, 2, 3]));
+        assert_eq!(
+            parse_all(&list, "1, 2,").map_err(|err| err.to_string()),
+            Err("expected a digit at column 5 but found end of input".to_string())
+        );
+    }
+
+    #[test]
+    fn test_delimited() {
+        let quoted = delimited(
+            literal("\""),
+            take_while1(|c| c != '"', "a letter"),
+            literal("\""),
+        );
+        assert_eq!(parse_all(&quoted, "\"a b\""), Ok("a b"));
+        assert_eq!(
+            parse_all(&quoted, "\"ab").map_err(|err| err.to_string()),
+            Err("expected \"\\\"\" at column 3 but found end of input".to_string())
+        );
+    }
+
+    #[test]
+    fn test_quoted() {
+        assert_eq!(
+            parse_all(quoted(), r#""say \"hi\"\\n\n""#),
+            Ok("say \"hi\"\\n\n".to_string())
+        );
+        assert_eq!(parse_all(quoted(), "\"\""), Ok(String::new()));
+        assert_eq!(
+            parse_all(quoted(), r#""a\tb""#).map_err(|err| err.to_string()),
+            Err("expected an escape sequence at column 2 but found \"\\\\tb\\\"\"".to_string())
+        );
+        assert_eq!(
+            parse_all(quoted(), "\"ab").map_err(|err| err.to_string()),
+            Err("expected \"\\\"\" at column 3 but found end of input".to_string())
+        );
+    }
+
+    #[test]
+    fn test_key_value() {
+        assert_eq!(
+            parse_all(
+                separated(key_value("=", signed()), literal(";")),
+                "red = 12;blue=-3"
+            ),
+            Ok(vec![("red", 12), ("blue", -3)])
+        );
+    }
+
+    #[test]
+    fn test_spanned_and_find_all() {
+        assert_eq!(
+            find_all(spanned(unsigned()), "467..114..é7"),
+            Ok(vec![
+                (467, Span { start: 0, end: 3 }),
+                (114, Span { start: 5, end: 8 }),
+                (7, Span { start: 11, end: 12 }),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_find_all_too_large() {
+        assert_eq!(
+            find_all(spanned(unsigned()), "1.123456789012345678901").map_err(|err| err.to_string()),
+            Err("number too large: 123456789012345678901 at column 2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_error_column() {
+        let name = take_while1(|c| c != ' ', "a name");
+        let err = parse_all(pair(name, literal(" "), unsigned()), "é 1x").unwrap_err();
+        assert_eq!(err.offset, 4);
+        assert_eq!(err.column, 3);
+        assert_eq!(
+            err.to_string(),
+            "expected end of input at column 3 but found \"x\""
+        );
+    }
+
+    #[test]
+    fn test_error_snippet() {
+        assert_eq!(
+            parse_all(word(), "abc 0123456789012").map_err(|err| err.to_string()),
+            Err("expected end of input at column 3 but found \" 012345678\"...".to_string())
+        );
+    }
+}
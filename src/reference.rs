@@ -0,0 +1,315 @@
+//! Deliberately simple solutions to every day and part, used to cross-check the real solvers.
+//!
+//! Everything here works on the raw puzzle text, shares no code with the solvers and favors
+//! obviously-correct loops over speed or error handling.
+
+const DIGIT_WORDS: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+fn day01(input: &str, spelled: bool) -> u64 {
+    let mut total = 0;
+    for line in input.lines().filter(|line| !line.is_empty()) {
+        let mut digits = vec![];
+        for i in 0..line.len() {
+            let rest = &line[i..];
+            for digit in 0..10 {
+                if rest.starts_with(&digit.to_string())
+                    || (spelled && digit > 0 && rest.starts_with(DIGIT_WORDS[digit as usize]))
+                {
+                    digits.push(digit);
+                }
+            }
+        }
+        total += digits[0] * 10 + digits[digits.len() - 1];
+    }
+    total
+}
+
+pub fn day01_part1(input: &str) -> u64 {
+    day01(input, false)
+}
+
+pub fn day01_part2(input: &str) -> u64 {
+    day01(input, true)
+}
+
+/// Game id and the most cubes of each color seen in any one draw, as (red, green, blue).
+fn day02_games(input: &str) -> Vec<(u64, [u64; 3])> {
+    let mut games = vec![];
+    for line in input.lines().filter(|line| !line.is_empty()) {
+        let (header, draws) = line.split_once(": ").unwrap();
+        let id = header
+            .strip_prefix("Game ")
+            .unwrap()
+            .parse::<u64>()
+            .unwrap();
+        let mut maxes = [0; 3];
+        for draw in draws.split("; ") {
+            for cubes in draw.split(", ") {
+                let (count, color) = cubes.split_once(' ').unwrap();
+                let count = count.parse::<u64>().unwrap();
+                let i = ["red", "green", "blue"]
+                    .iter()
+                    .position(|c| *c == color)
+                    .unwrap();
+                if count > maxes[i] {
+                    maxes[i] = count;
+                }
+            }
+        }
+        games.push((id, maxes));
+    }
+    games
+}
+
+/// Sum of the ids of games possible with `bag` cubes of each color, as (red, green, blue).
+pub fn day02_part1(input: &str, bag: [u64; 3]) -> u64 {
+    let mut total = 0;
+    for (id, maxes) in day02_games(input) {
+        if maxes[0] <= bag[0] && maxes[1] <= bag[1] && maxes[2] <= bag[2] {
+            total += id;
+        }
+    }
+    total
+}
+
+pub fn day02_part2(input: &str) -> u64 {
+    let mut total = 0;
+    for (_, maxes) in day02_games(input) {
+        total += maxes[0] * maxes[1] * maxes[2];
+    }
+    total
+}
+
+/// Every number in the schematic as (line, first column, last column, value).
+fn day03_numbers(grid: &[Vec<char>]) -> Vec<(usize, usize, usize, u64)> {
+    let mut numbers = vec![];
+    for (line, row) in grid.iter().enumerate() {
+        let mut col = 0;
+        while col < row.len() {
+            if row[col].is_ascii_digit() {
+                let start = col;
+                let mut value = 0;
+                while col < row.len() && row[col].is_ascii_digit() {
+                    value = value * 10 + row[col].to_digit(10).unwrap() as u64;
+                    col += 1;
+                }
+                numbers.push((line, start, col - 1, value));
+            } else {
+                col += 1;
+            }
+        }
+    }
+    numbers
+}
+
+fn day03_grid(input: &str) -> Vec<Vec<char>> {
+    input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.chars().collect())
+        .collect()
+}
+
+/// Whether the cell at (`line`, `col`) is in the box one cell around the number.
+fn day03_borders(number: &(usize, usize, usize, u64), line: usize, col: usize) -> bool {
+    let (num_line, first, last, _) = *number;
+    line + 1 >= num_line && line <= num_line + 1 && col + 1 >= first && col <= last + 1
+}
+
+pub fn day03_part1(input: &str) -> u64 {
+    let grid = day03_grid(input);
+    let mut total = 0;
+    for number in day03_numbers(&grid) {
+        let mut is_part = false;
+        for (line, row) in grid.iter().enumerate() {
+            for (col, c) in row.iter().enumerate() {
+                if *c != '.' && !c.is_ascii_digit() && day03_borders(&number, line, col) {
+                    is_part = true;
+                }
+            }
+        }
+        if is_part {
+            total += number.3;
+        }
+    }
+    total
+}
+
+pub fn day03_part2(input: &str) -> u64 {
+    let grid = day03_grid(input);
+    let numbers = day03_numbers(&grid);
+    let mut total = 0;
+    for (line, row) in grid.iter().enumerate() {
+        for (col, c) in row.iter().enumerate() {
+            if *c != '*' {
+                continue;
+            }
+            let adjacent = numbers
+                .iter()
+                .filter(|number| day03_borders(number, line, col))
+                .collect::<Vec<_>>();
+            if adjacent.len() == 2 {
+                total += adjacent[0].3 * adjacent[1].3;
+            }
+        }
+    }
+    total
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::arith::checked_sum;
+    use crate::day01::{get_calibration, get_spelled_calibration};
+    use crate::day02::eval::{evaluate, Answers};
+    use crate::day02::generate::{generate as generate_log, GeneratorConfig as LogConfig};
+    use crate::day02::{Draw, PUZZLE_BAG};
+    use crate::day03::generate::{generate as generate_schematic, GeneratorConfig};
+    use crate::day03::neighborhood::Neighborhood;
+    use crate::day03::stream::solve_stream;
+    use crate::day03::symbols::SymbolSet;
+    use crate::day03::{get_gear_ratios, get_part_nums, get_symbols, get_unique_part_numbers};
+    use crate::rng::Rng;
+
+    fn check_day01(input: &str) {
+        let lines = input.lines().filter(|line| !line.is_empty());
+        assert_eq!(
+            lines
+                .clone()
+                .map(|line| get_calibration(line).unwrap() as u64)
+                .sum::<u64>(),
+            day01_part1(input)
+        );
+        assert_eq!(
+            lines
+                .map(|line| get_spelled_calibration(line).unwrap() as u64)
+                .sum::<u64>(),
+            day01_part2(input)
+        );
+    }
+
+    fn check_day02(input: &str, bag: Draw) {
+        assert_eq!(
+            evaluate(input.as_bytes(), bag),
+            Ok(Answers {
+                possible_id_sum: day02_part1(input, [bag.red, bag.green, bag.blue]),
+                total_power: day02_part2(input),
+            })
+        );
+    }
+
+    fn check_day03(input: &str) {
+        let lines = input
+            .lines()
+            .map(|line| line.to_string())
+            .collect::<Vec<String>>();
+        let symbol_set = SymbolSet::default();
+        let neighborhood = Neighborhood::default();
+        let part_nums = get_part_nums(&lines, &symbol_set).unwrap();
+        let symbols = get_symbols(&lines, &symbol_set).unwrap();
+        let expected = (day03_part1(input), day03_part2(input));
+        assert_eq!(
+            (
+                checked_sum(get_unique_part_numbers(&part_nums, &symbols, &neighborhood)).unwrap(),
+                checked_sum(get_gear_ratios(&part_nums, &symbols, &neighborhood).unwrap()).unwrap()
+            ),
+            expected
+        );
+        assert_eq!(
+            solve_stream(input.as_bytes(), symbol_set, |_| ()),
+            Ok(expected)
+        );
+    }
+
+    #[test]
+    fn test_fixtures() {
+        check_day01(include_str!("bin/day01.txt"));
+        check_day02(include_str!("bin/day02.txt"), PUZZLE_BAG);
+        check_day03(include_str!("bin/day03.txt"));
+    }
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(
+            day01_part1("1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n"),
+            142
+        );
+        assert_eq!(
+            day01_part2(
+                "two1nine\neightwothree\nabcone2threexyz\nxtwone3four\n4nineeightseven2\n\
+                 zoneight234\n7pqrstsixteen\n"
+            ),
+            281
+        );
+        let games = crate::day02::EXAMPLE;
+        assert_eq!(day02_part1(games, [12, 13, 14]), 8);
+        assert_eq!(day02_part2(games), 2286);
+        let schematic = crate::day03::EXAMPLE;
+        assert_eq!(day03_part1(schematic), 4361);
+        assert_eq!(day03_part2(schematic), 467835);
+        check_day02(games, PUZZLE_BAG);
+        check_day03(schematic);
+    }
+
+    #[test]
+    fn test_generated_day01() {
+        let mut rng = Rng::new(1);
+        let pieces = DIGIT_WORDS[1..]
+            .iter()
+            .copied()
+            .chain(["0", "1", "5", "9", "a", "x", "ne", "tw", "eigh"])
+            .collect::<Vec<&str>>();
+        let input = (0..500)
+            .map(|_| {
+                // every line needs at least one plain digit for part 1
+                let mut line = (0..rng.range(0, 8))
+                    .map(|_| pieces[rng.range(0, pieces.len() as u64 - 1) as usize])
+                    .collect::<String>();
+                line.insert(
+                    rng.range(0, line.len() as u64) as usize,
+                    (b'0' + rng.range(0, 9) as u8) as char,
+                );
+                line + "\n"
+            })
+            .collect::<String>();
+        check_day01(&input);
+    }
+
+    #[test]
+    fn test_generated_day02() {
+        (0..10).for_each(|seed| {
+            let bag = Draw {
+                red: 5 + seed,
+                green: 10,
+                blue: 15 - seed,
+            };
+            let generated = generate_log(
+                &LogConfig {
+                    seed,
+                    ..LogConfig::default()
+                },
+                &bag,
+            )
+            .unwrap();
+            check_day02(&generated.log, bag);
+        })
+    }
+
+    #[test]
+    fn test_generated_day03() {
+        (0..10).for_each(|seed| {
+            let generated = generate_schematic(&GeneratorConfig {
+                seed,
+                height: 50,
+                width: 50,
+                density: 0.25,
+                symbol_chance: 0.2,
+                ..GeneratorConfig::default()
+            })
+            .unwrap();
+            check_day03(&generated.lines.join("\n"));
+        })
+    }
+}
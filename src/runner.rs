@@ -0,0 +1,56 @@
+use std::error::Error;
+
+/// What a solver returns for one part: the answer as it would be submitted, or why it failed.
+pub type Answer = Result<String, Box<dyn Error>>;
+
+pub type Solver = fn(&str) -> Answer;
+
+/// A registered day and its two solvers, which take the whole puzzle input.
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub day: u32,
+    pub part1: Solver,
+    pub part2: Solver,
+}
+
+impl Day {
+    pub const fn new(day: u32, part1: Solver, part2: Solver) -> Day {
+        Day { day, part1, part2 }
+    }
+
+    /// Path of the puzzle input, relative to the crate root.
+    pub fn input_path(&self) -> String {
+        format!("src/bin/day{:02}.txt", self.day)
+    }
+
+    /// Directory holding the example inputs, relative to the crate root.
+    pub fn fixture_dir(&self) -> String {
+        format!("fixtures/day{:02}", self.day)
+    }
+}
+
+/// Every day with a solver, in order. `aoc new` adds entries here.
+pub const DAYS: &[Day] = &[
+    Day::new(1, crate::day01::part1, crate::day01::part2),
+    Day::new(2, crate::day02::part1, crate::day02::part2),
+    Day::new(3, crate::day03::part1, crate::day03::part2),
+];
+
+pub fn find_day(day: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|registered| registered.day == day)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_days_sorted() {
+        assert!(DAYS.windows(2).all(|pair| pair[0].day < pair[1].day));
+        assert_eq!(
+            find_day(2).map(|day| day.input_path()),
+            Some("src/bin/day02.txt".to_string())
+        );
+        assert!(find_day(25).is_none());
+    }
+}
@@ -0,0 +1,269 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Why a new day could not be created. Nothing is written when any of these are returned.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ScaffoldError {
+    InvalidDay(u32),
+    DayExists {
+        day: u32,
+        path: String,
+    },
+    /// A file that new days are registered in has no existing entries to place the new one by.
+    MissingRegistry(String),
+    Io(String),
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaffoldError::InvalidDay(day) => write!(f, "day must be between 1 and 25: {}", day),
+            ScaffoldError::DayExists { day, path } => {
+                write!(f, "day {} already exists: {}", day, path)
+            }
+            ScaffoldError::MissingRegistry(path) => {
+                write!(f, "unable to find where to register the day in {}", path)
+            }
+            ScaffoldError::Io(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {}
+
+/// Source of a new solver module with a fallible line parser, placeholder parts and ignored
+/// example tests.
+pub fn solver_module(day: u32) -> String {
+    format!(
+        r#"use crate::runner::Answer;
+
+/// The value of one line of input, or `None` if it cannot be read.
+pub fn parse_line(line: &str) -> Option<&str> {{
+    Some(line)
+}}
+
+fn parse_lines(input: &str) -> Result<Vec<&str>, String> {{
+    input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| parse_line(line).ok_or_else(|| format!("unable to read line: {{}}", line)))
+        .collect()
+}}
+
+pub fn part1(input: &str) -> Answer {{
+    let lines = parse_lines(input)?;
+    Err(format!("part 1 is not solved yet ({{}} lines of input)", lines.len()).into())
+}}
+
+pub fn part2(input: &str) -> Answer {{
+    let lines = parse_lines(input)?;
+    Err(format!("part 2 is not solved yet ({{}} lines of input)", lines.len()).into())
+}}
+
+#[cfg(test)]
+mod test {{
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../fixtures/day{day:02}/example.txt");
+
+    #[test]
+    #[ignore = "fill in the example and its expected answer"]
+    fn test_part1() {{
+        assert_eq!(part1(EXAMPLE).unwrap(), "");
+    }}
+
+    #[test]
+    #[ignore = "fill in the example and its expected answer"]
+    fn test_part2() {{
+        assert_eq!(part2(EXAMPLE).unwrap(), "");
+    }}
+}}
+"#,
+        day = day
+    )
+}
+
+/// Insert `line` among the lines for which `key` returns a day, keeping them ordered by day.
+fn register(text: &str, key: impl Fn(&str) -> Option<u32>, day: u32, line: &str) -> Option<String> {
+    let lines = text.lines().collect::<Vec<&str>>();
+    let entries = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| key(line).map(|entry_day| (i, entry_day)))
+        .collect::<Vec<(usize, u32)>>();
+    let (last_i, _) = entries.last()?;
+    let insert_i = entries
+        .iter()
+        .find(|(_, entry_day)| *entry_day > day)
+        .map_or(last_i + 1, |(i, _)| *i);
+
+    let mut out = lines[..insert_i].join("\n");
+    if insert_i > 0 {
+        out.push('\n');
+    }
+    out.push_str(line);
+    out.push('\n');
+    out.push_str(&lines[insert_i..].join("\n"));
+    if text.ends_with('\n') && insert_i < lines.len() {
+        out.push('\n');
+    }
+    Some(out)
+}
+
+fn lib_key(line: &str) -> Option<u32> {
+    line.strip_prefix("pub mod day")?
+        .strip_suffix(';')?
+        .parse()
+        .ok()
+}
+
+fn runner_key(line: &str) -> Option<u32> {
+    line.trim()
+        .strip_prefix("Day::new(")?
+        .split_once(',')?
+        .0
+        .parse()
+        .ok()
+}
+
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    std::fs::read_to_string(path)
+        .map_err(|err| ScaffoldError::Io(format!("unable to read {}: {}", path.display(), err)))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), ScaffoldError> {
+    std::fs::write(path, contents)
+        .map_err(|err| ScaffoldError::Io(format!("unable to write {}: {}", path.display(), err)))
+}
+
+/// Create the solver module and example fixture for `day` under the crate at `root`, and
+/// register it in `src/lib.rs` and the runner. Returns every file created or changed.
+pub fn new_day(root: &Path, day: u32) -> Result<Vec<PathBuf>, ScaffoldError> {
+    if !(1..=25).contains(&day) {
+        return Err(ScaffoldError::InvalidDay(day));
+    }
+    let module_path = root.join(format!("src/day{:02}.rs", day));
+    let fixture_dir = root.join(format!("fixtures/day{:02}", day));
+    let lib_path = root.join("src/lib.rs");
+    let runner_path = root.join("src/runner.rs");
+
+    // check everything before writing anything, so a refusal leaves the tree untouched
+    for path in [&module_path, &fixture_dir] {
+        if path.exists() {
+            return Err(ScaffoldError::DayExists {
+                day,
+                path: path.display().to_string(),
+            });
+        }
+    }
+    let lib = read(&lib_path)?;
+    let runner = read(&runner_path)?;
+    for (path, text, key) in [
+        (&lib_path, &lib, lib_key as fn(&str) -> Option<u32>),
+        (&runner_path, &runner, runner_key),
+    ] {
+        if text.lines().any(|line| key(line) == Some(day)) {
+            return Err(ScaffoldError::DayExists {
+                day,
+                path: path.display().to_string(),
+            });
+        }
+    }
+    let lib = register(&lib, lib_key, day, &format!("pub mod day{:02};", day))
+        .ok_or_else(|| ScaffoldError::MissingRegistry(lib_path.display().to_string()))?;
+    let runner = register(
+        &runner,
+        runner_key,
+        day,
+        &format!(
+            "    Day::new({day}, crate::day{day:02}::part1, crate::day{day:02}::part2),",
+            day = day
+        ),
+    )
+    .ok_or_else(|| ScaffoldError::MissingRegistry(runner_path.display().to_string()))?;
+
+    std::fs::create_dir_all(&fixture_dir).map_err(|err| {
+        ScaffoldError::Io(format!(
+            "unable to create {}: {}",
+            fixture_dir.display(),
+            err
+        ))
+    })?;
+    let example_path = fixture_dir.join("example.txt");
+    write(&example_path, "")?;
+    write(&module_path, &solver_module(day))?;
+    write(&lib_path, &lib)?;
+    write(&runner_path, &runner)?;
+    Ok(vec![module_path, example_path, lib_path, runner_path])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::runner::DAYS;
+
+    #[test]
+    fn test_register() {
+        let text = "pub mod cli;\npub mod day01;\npub mod day03;\npub mod grid;\n";
+        assert_eq!(
+            register(text, lib_key, 2, "pub mod day02;"),
+            Some(
+                "pub mod cli;\npub mod day01;\npub mod day02;\npub mod day03;\npub mod grid;\n"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            register(text, lib_key, 12, "pub mod day12;"),
+            Some(
+                "pub mod cli;\npub mod day01;\npub mod day03;\npub mod day12;\npub mod grid;\n"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            register("pub mod cli;\n", lib_key, 1, "pub mod day01;"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/lib.rs"), include_str!("lib.rs")).unwrap();
+        std::fs::write(root.join("src/runner.rs"), include_str!("runner.rs")).unwrap();
+
+        // the day after the last registered one, so this keeps passing as days are added
+        let last = DAYS.last().unwrap().day;
+        let day = last + 1;
+        assert_eq!(new_day(&root, day).map(|paths| paths.len()), Ok(4));
+        let lib = std::fs::read_to_string(root.join("src/lib.rs")).unwrap();
+        assert!(lib.contains(&format!(
+            "pub mod day{:02};\npub mod day{:02};\n",
+            last, day
+        )));
+        let runner = std::fs::read_to_string(root.join("src/runner.rs")).unwrap();
+        assert!(runner.contains(&format!(
+            "    Day::new({day}, crate::day{day:02}::part1, crate::day{day:02}::part2),\n];",
+            day = day
+        )));
+        assert!(root.join(format!("src/day{:02}.rs", day)).exists());
+        assert!(root
+            .join(format!("fixtures/day{:02}/example.txt", day))
+            .exists());
+
+        for day in [last, day] {
+            assert!(matches!(
+                new_day(&root, day),
+                Err(ScaffoldError::DayExists { .. })
+            ));
+        }
+        assert_eq!(new_day(&root, 26), Err(ScaffoldError::InvalidDay(26)));
+        // a refused day leaves the registry untouched
+        assert_eq!(
+            std::fs::read_to_string(root.join("src/lib.rs")).unwrap(),
+            lib
+        );
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
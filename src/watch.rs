@@ -0,0 +1,257 @@
+//! Polling-based change detection and timed runs for `aoc watch`.
+//!
+//! Files are compared by modification time and length on every poll, so no OS notification
+//! service is needed.
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::runner::{Day, Solver};
+
+type Stamp = Option<(SystemTime, u64)>;
+
+/// Watches a set of files plus every file in a set of directories, so fixtures added while
+/// watching are picked up too.
+#[derive(Debug)]
+pub struct Watcher {
+    files: Vec<PathBuf>,
+    dirs: Vec<PathBuf>,
+    stamps: BTreeMap<PathBuf, Stamp>,
+}
+
+impl Watcher {
+    pub fn new(files: Vec<PathBuf>, dirs: Vec<PathBuf>) -> Watcher {
+        let mut watcher = Watcher {
+            files,
+            dirs,
+            stamps: BTreeMap::new(),
+        };
+        watcher.stamps = watcher.snapshot();
+        watcher
+    }
+
+    fn snapshot(&self) -> BTreeMap<PathBuf, Stamp> {
+        let dir_files = self.dirs.iter().flat_map(|dir| {
+            std::fs::read_dir(dir)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.is_file())
+        });
+        self.files
+            .iter()
+            .cloned()
+            .chain(dir_files)
+            .map(|path| {
+                let stamp = std::fs::metadata(&path)
+                    .ok()
+                    .and_then(|meta| Some((meta.modified().ok()?, meta.len())));
+                (path, stamp)
+            })
+            .collect()
+    }
+
+    /// Every watched path that appeared, disappeared or changed since the last poll.
+    pub fn poll(&mut self) -> Vec<PathBuf> {
+        let stamps = self.snapshot();
+        let mut changed = stamps
+            .iter()
+            .filter(|(path, stamp)| self.stamps.get(*path) != Some(stamp))
+            .map(|(path, _)| path.clone())
+            .chain(
+                self.stamps
+                    .keys()
+                    .filter(|path| !stamps.contains_key(*path))
+                    .cloned(),
+            )
+            .collect::<Vec<PathBuf>>();
+        changed.sort();
+        changed.dedup();
+        self.stamps = stamps;
+        changed
+    }
+
+    /// Every file currently watched, in path order.
+    pub fn paths(&self) -> Vec<&Path> {
+        self.stamps.keys().map(|path| path.as_path()).collect()
+    }
+}
+
+/// The outcome of running one part on one input.
+#[derive(Debug)]
+pub struct Run {
+    pub source: String,
+    pub part: u32,
+    pub answer: Result<String, String>,
+    pub elapsed: Duration,
+}
+
+impl fmt::Display for Run {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let elapsed = format!("{:.3}ms", self.elapsed.as_secs_f64() * 1000.0);
+        match &self.answer {
+            Ok(answer) => write!(
+                f,
+                "{} part {}: {} ({})",
+                self.source, self.part, answer, elapsed
+            ),
+            Err(err) => write!(
+                f,
+                "{} part {}: error: {} ({})",
+                self.source, self.part, err, elapsed
+            ),
+        }
+    }
+}
+
+/// The message a panic was raised with, if it was a string.
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<String>()
+        .cloned()
+        .or_else(|| payload.downcast_ref::<&str>().map(|msg| msg.to_string()))
+        .unwrap_or_else(|| "solver panicked".to_string())
+}
+
+/// Run `solver` and time it, turning both errors and panics into an error message so a bad
+/// input cannot end the watch. The panic hook is left alone, so a panic is still reported on
+/// stderr with its location.
+pub fn run_part(solver: Solver, input: &str) -> (Result<String, String>, Duration) {
+    let start = Instant::now();
+    let answer = match catch_unwind(AssertUnwindSafe(|| solver(input))) {
+        Ok(Ok(answer)) => Ok(answer),
+        Ok(Err(err)) => Err(err.to_string()),
+        Err(payload) => Err(panic_message(payload.as_ref())),
+    };
+    (answer, start.elapsed())
+}
+
+/// Which parts to run on a fixture: `example-part1.txt` only runs part 1, `example-part2.txt`
+/// only part 2, and any other file both.
+pub fn fixture_parts(path: &Path) -> Vec<u32> {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy())
+        .unwrap_or_default();
+    if stem.ends_with("part1") {
+        vec![1]
+    } else if stem.ends_with("part2") {
+        vec![2]
+    } else {
+        vec![1, 2]
+    }
+}
+
+/// Run both parts of `day` on every example fixture and then on the puzzle input, relative to
+/// the crate at `root`.
+pub fn run_day(root: &Path, day: &Day) -> Vec<Run> {
+    let mut fixtures = std::fs::read_dir(root.join(day.fixture_dir()))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .collect::<Vec<PathBuf>>();
+    fixtures.sort();
+    let sources = fixtures
+        .into_iter()
+        .map(|path| {
+            let parts = fixture_parts(&path);
+            (path, parts)
+        })
+        .chain([(root.join(day.input_path()), vec![1, 2])]);
+
+    let mut out = vec![];
+    for (path, parts) in sources {
+        let source = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .display()
+            .to_string();
+        let input = std::fs::read_to_string(&path);
+        for part in parts {
+            let (answer, elapsed) = match &input {
+                Ok(input) => run_part(if part == 1 { day.part1 } else { day.part2 }, input),
+                Err(err) => (Err(format!("unable to read: {}", err)), Duration::ZERO),
+            };
+            out.push(Run {
+                source: source.clone(),
+                part,
+                answer,
+                elapsed,
+            });
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::runner::{find_day, Answer};
+
+    fn fails(_: &str) -> Answer {
+        Err("not solved".into())
+    }
+
+    #[test]
+    fn test_watcher_poll() {
+        let dir = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        let fixtures = dir.join("fixtures");
+        std::fs::create_dir_all(&fixtures).unwrap();
+        let input = dir.join("input.txt");
+        std::fs::write(&input, "1").unwrap();
+
+        let mut watcher = Watcher::new(vec![input.clone()], vec![fixtures.clone()]);
+        assert_eq!(watcher.poll(), Vec::<PathBuf>::new());
+        // lengths differ, so this is seen even where modification times are coarse
+        std::fs::write(&input, "12").unwrap();
+        assert_eq!(watcher.poll(), vec![input.clone()]);
+        let example = fixtures.join("example.txt");
+        std::fs::write(&example, "3").unwrap();
+        assert_eq!(watcher.poll(), vec![example.clone()]);
+        std::fs::remove_file(&example).unwrap();
+        std::fs::remove_file(&input).unwrap();
+        assert_eq!(watcher.poll(), vec![example, input.clone()]);
+        assert_eq!(watcher.paths(), vec![input.as_path()]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_panic_message() {
+        assert_eq!(panic_message(&"bad input"), "bad input");
+        assert_eq!(panic_message(&format!("bad {}", "line")), "bad line");
+        assert_eq!(panic_message(&42), "solver panicked");
+    }
+
+    #[test]
+    fn test_run_part() {
+        assert_eq!(run_part(fails, "").0, Err("not solved".to_string()));
+    }
+
+    #[test]
+    fn test_run_day() {
+        let runs = run_day(Path::new("."), find_day(1).unwrap());
+        assert_eq!(
+            runs.iter()
+                .map(|run| (run.source.as_str(), run.part, run.answer.clone()))
+                .collect::<Vec<(&str, u32, Result<String, String>)>>(),
+            vec![
+                ("fixtures/day01/example-part1.txt", 1, Ok("142".to_string())),
+                ("fixtures/day01/example-part2.txt", 2, Ok("281".to_string())),
+                ("src/bin/day01.txt", 1, Ok("54561".to_string())),
+                ("src/bin/day01.txt", 2, Ok("54076".to_string())),
+            ]
+        );
+        assert_eq!(
+            fixture_parts(Path::new("fixtures/day02/example.txt")),
+            vec![1, 2]
+        );
+    }
+}